@@ -1,4 +1,5 @@
 use crate::utils::*;
+use crate::font::Font;
 pub mod error;
 pub mod dragon_idle_01;
 pub mod dragon_walk_01;
@@ -8,13 +9,49 @@ pub mod dragon_jump_01;
 pub mod dragon_fly_01;
 pub mod dragon_fly_02;
 pub mod dragon_fall_01;
+pub mod dragon_breath_01;
+pub mod dragon_wall_slide_01;
+pub mod dragon_wall_jump_01;
+pub mod dragon_hurt_01;
 pub mod tile_wall;
+pub mod tile_wall_cracked;
+pub mod tile_oneway;
+pub mod tile_crumble_01;
+pub mod tile_crumble_02;
+pub mod tile_conveyor_left;
+pub mod tile_conveyor_right;
+pub mod tile_ice;
+pub mod tile_gravity_up;
+pub mod tile_gravity_down;
+pub mod tile_stamina;
+pub mod tile_checkpoint_off;
+pub mod tile_checkpoint_on;
+pub mod item_heart;
+pub mod hud_heart_full;
+pub mod hud_heart_empty;
 pub mod tile_needle;
 pub mod tile_spring;
 pub mod tile_gate_open;
 pub mod tile_gate_close;
 pub mod item_key;
 pub mod item_fragment;
+pub mod font;
+pub mod boss_float_01;
+pub mod boss_float_02;
+pub mod boss_angry_01;
+pub mod boss_stun_01;
+pub mod boss_shot;
+pub mod enemy_walker_01;
+pub mod enemy_walker_02;
+pub mod enemy_flyer_01;
+pub mod enemy_flyer_02;
+pub mod enemy_turret_01;
+pub mod enemy_shot_01;
+pub mod breath_01;
+pub mod breath_02;
+pub mod wall_break_01;
+pub mod wall_break_02;
+pub mod platform_01;
 use error::*;
 use dragon_idle_01::*;
 use dragon_walk_01::*;
@@ -24,13 +61,49 @@ use dragon_jump_01::*;
 use dragon_fly_01::*;
 use dragon_fly_02::*;
 use dragon_fall_01::*;
+use dragon_breath_01::*;
+use dragon_wall_slide_01::*;
+use dragon_wall_jump_01::*;
+use dragon_hurt_01::*;
 use tile_wall::*;
+use tile_wall_cracked::*;
+use tile_oneway::*;
+use tile_crumble_01::*;
+use tile_crumble_02::*;
+use tile_conveyor_left::*;
+use tile_conveyor_right::*;
+use tile_ice::*;
+use tile_gravity_up::*;
+use tile_gravity_down::*;
+use tile_stamina::*;
+use tile_checkpoint_off::*;
+use tile_checkpoint_on::*;
+use item_heart::*;
+use hud_heart_full::*;
+use hud_heart_empty::*;
 use tile_needle::*;
 use tile_spring::*;
 use tile_gate_open::*;
 use tile_gate_close::*;
 use item_key::*;
 use item_fragment::*;
+use font::*;
+use boss_float_01::*;
+use boss_float_02::*;
+use boss_angry_01::*;
+use boss_stun_01::*;
+use boss_shot::*;
+use enemy_walker_01::*;
+use enemy_walker_02::*;
+use enemy_flyer_01::*;
+use enemy_flyer_02::*;
+use enemy_turret_01::*;
+use enemy_shot_01::*;
+use breath_01::*;
+use breath_02::*;
+use wall_break_01::*;
+use wall_break_02::*;
+use platform_01::*;
 
 pub const IMG_ERROR:                  Image = Image::new( 0x0432, &RAWIMG_ERROR);
 
@@ -42,12 +115,28 @@ pub const IMG_DRAGON_JUMP_01:         Image = Image::new( 0x0432, &RAWIMG_DRAGON
 pub const IMG_DRAGON_FLY_01:          Image = Image::new( 0x0432, &RAWIMG_DRAGON_FLY_01);
 pub const IMG_DRAGON_FLY_02:          Image = Image::new( 0x0432, &RAWIMG_DRAGON_FLY_02);
 pub const IMG_DRAGON_FALL_01:         Image = Image::new( 0x0432, &RAWIMG_DRAGON_FALL_01);
+pub const IMG_DRAGON_BREATH_01:       Image = Image::new( 0x0432, &RAWIMG_DRAGON_BREATH_01);
+pub const IMG_DRAGON_WALL_SLIDE_01:   Image = Image::new( 0x0432, &RAWIMG_DRAGON_WALL_SLIDE_01);
+pub const IMG_DRAGON_WALL_JUMP_01:    Image = Image::new( 0x0432, &RAWIMG_DRAGON_WALL_JUMP_01);
+pub const IMG_DRAGON_HURT_01:         Image = Image::new( 0x0432, &RAWIMG_DRAGON_HURT_01);
 pub const IMG_DRAGON_DEATH_01:        Image = Image::new( 0x0432, &RAWIMG_DRAGON_FLY_01);
 pub const IMG_DRAGON_DEATH_02:        Image = Image::newf( 0x0432, &RAWIMG_DRAGON_FLY_02, false, false, true);
 pub const IMG_DRAGON_DEATH_03:        Image = Image::newf( 0x0432, &RAWIMG_DRAGON_FLY_01, true, true, false);
 pub const IMG_DRAGON_DEATH_04:        Image = Image::newf( 0x0432, &RAWIMG_DRAGON_FLY_02, false, true, true);
 
 pub const IMG_TILE_WALL:              Image = Image::new( 0x1243, &RAWIMG_TILE_WALL);
+pub const IMG_TILE_WALL_CRACKED:      Image = Image::new( 0x1243, &RAWIMG_TILE_WALL_CRACKED);
+pub const IMG_TILE_ONEWAY:            Image = Image::new( 0x2340, &RAWIMG_TILE_ONEWAY);
+pub const IMG_TILE_CRUMBLE_01:        Image = Image::new( 0x1243, &RAWIMG_TILE_CRUMBLE_01);
+pub const IMG_TILE_CRUMBLE_02:        Image = Image::new( 0x1243, &RAWIMG_TILE_CRUMBLE_02);
+pub const IMG_TILE_CONVEYOR_LEFT:     Image = Image::new( 0x1243, &RAWIMG_TILE_CONVEYOR_LEFT);
+pub const IMG_TILE_CONVEYOR_RIGHT:    Image = Image::new( 0x1243, &RAWIMG_TILE_CONVEYOR_RIGHT);
+pub const IMG_TILE_ICE:               Image = Image::new( 0x1342, &RAWIMG_TILE_ICE);
+pub const IMG_TILE_GRAVITY_UP:        Image = Image::new( 0x0020, &RAWIMG_TILE_GRAVITY_UP);
+pub const IMG_TILE_GRAVITY_DOWN:      Image = Image::new( 0x0020, &RAWIMG_TILE_GRAVITY_DOWN);
+pub const IMG_TILE_STAMINA:           Image = Image::new( 0x2340, &RAWIMG_TILE_STAMINA);
+pub const IMG_TILE_CHECKPOINT_OFF:    Image = Image::new( 0x2340, &RAWIMG_TILE_CHECKPOINT_OFF);
+pub const IMG_TILE_CHECKPOINT_ON:     Image = Image::new( 0x2340, &RAWIMG_TILE_CHECKPOINT_ON);
 pub const IMG_TILE_NEEDLE_UP:         Image = Image::newf(0x4320, &RAWIMG_TILE_NEEDLE, false, false, false);
 pub const IMG_TILE_NEEDLE_RIGHT:      Image = Image::newf(0x4320, &RAWIMG_TILE_NEEDLE, false, true, true);
 pub const IMG_TILE_NEEDLE_LEFT:       Image = Image::newf(0x4320, &RAWIMG_TILE_NEEDLE, false, false, true);
@@ -58,8 +147,36 @@ pub const IMG_TILE_GATE_OPEN:         Image = Image::new( 0x0040, &RAWIMG_TILE_G
 pub const IMG_TILE_GATE_CLOSE:        Image = Image::new( 0x4320, &RAWIMG_TILE_GATE_CLOSE);
 pub const IMG_ITEM_KEY:               Image = Image::new( 0x4320, &RAWIMG_ITEM_KEY);
 pub const IMG_ITEM_FRAGMENT:          Image = Image::new( 0x4320, &RAWIMG_ITEM_FRAGMENT);
+pub const IMG_ITEM_HEART:             Image = Image::new( 0x2340, &RAWIMG_ITEM_HEART);
 
-pub static TILE_STAGE: &[&'static Image; 13] = &[
+pub const IMG_BOSS_FLOAT_01:          Image = Image::new( 0x2340, &RAWIMG_BOSS_FLOAT_01);
+pub const IMG_BOSS_FLOAT_02:          Image = Image::new( 0x2340, &RAWIMG_BOSS_FLOAT_02);
+pub const IMG_BOSS_ANGRY_01:          Image = Image::new( 0x2340, &RAWIMG_BOSS_ANGRY_01);
+pub const IMG_BOSS_STUN_01:           Image = Image::new( 0x2340, &RAWIMG_BOSS_STUN_01);
+pub const IMG_BOSS_STUN_02:           Image = Image::newf(0x2340, &RAWIMG_BOSS_STUN_01, true, false, false);
+pub const IMG_BOSS_SHOT:              Image = Image::new( 0x2340, &RAWIMG_BOSS_SHOT);
+
+pub const IMG_ENEMY_WALKER_01:        Image = Image::new( 0x2340, &RAWIMG_ENEMY_WALKER_01);
+pub const IMG_ENEMY_WALKER_02:        Image = Image::new( 0x2340, &RAWIMG_ENEMY_WALKER_02);
+pub const IMG_ENEMY_FLYER_01:         Image = Image::new( 0x2340, &RAWIMG_ENEMY_FLYER_01);
+pub const IMG_ENEMY_FLYER_02:         Image = Image::new( 0x2340, &RAWIMG_ENEMY_FLYER_02);
+pub const IMG_ENEMY_TURRET_01:        Image = Image::new( 0x2340, &RAWIMG_ENEMY_TURRET_01);
+pub const IMG_ENEMY_SHOT_01:          Image = Image::new( 0x0040, &RAWIMG_ENEMY_SHOT_01);
+pub const IMG_BREATH_01:              Image = Image::new( 0x2340, &RAWIMG_BREATH_01);
+pub const IMG_BREATH_02:              Image = Image::new( 0x2340, &RAWIMG_BREATH_02);
+pub const IMG_WALL_BREAK_01:          Image = Image::new( 0x0040, &RAWIMG_WALL_BREAK_01);
+pub const IMG_WALL_BREAK_02:          Image = Image::new( 0x0040, &RAWIMG_WALL_BREAK_02);
+pub const IMG_PLATFORM_01:            Image = Image::new( 0x2340, &RAWIMG_PLATFORM_01);
+
+pub const IMG_HUD_HEART_FULL:         Image = Image::new( 0x2340, &RAWIMG_HUD_HEART_FULL);
+pub const IMG_HUD_HEART_EMPTY:        Image = Image::new( 0x2340, &RAWIMG_HUD_HEART_EMPTY);
+
+pub const IMG_FONT:                   Image = Image::new( 0x0040, &RAWIMG_FONT);
+pub const IMG_FONT_HUD:               Image = Image::new( 0x0024, &RAWIMG_FONT);
+pub const FONT_MAIN:                  Font  = Font::new(&IMG_FONT);
+pub const FONT_HUD:                   Font  = Font::new(&IMG_FONT_HUD);
+
+pub static TILE_STAGE: &[&'static Image; 25] = &[
   &IMG_ERROR,                  // Empty
   &IMG_ERROR,                  // Start
   &IMG_TILE_WALL,              // Wall
@@ -73,6 +190,18 @@ pub static TILE_STAGE: &[&'static Image; 13] = &[
   &IMG_TILE_GATE_OPEN,         // Goal(opened)
   &IMG_ITEM_KEY,               // Key
   &IMG_ITEM_FRAGMENT,          // Fragment
+  &IMG_TILE_WALL_CRACKED,      // Cracked Wall
+  &IMG_TILE_ONEWAY,            // One-way
+  &IMG_TILE_CRUMBLE_01,        // Crumble
+  &IMG_TILE_CONVEYOR_LEFT,     // Conveyor Left
+  &IMG_TILE_CONVEYOR_RIGHT,    // Conveyor Right
+  &IMG_TILE_ICE,               // Ice
+  &IMG_TILE_GRAVITY_UP,        // Gravity Up
+  &IMG_TILE_GRAVITY_DOWN,      // Gravity Down
+  &IMG_TILE_STAMINA,           // Stamina
+  &IMG_TILE_CHECKPOINT_OFF,    // Checkpoint
+  &IMG_TILE_CHECKPOINT_ON,     // Checkpoint Active
+  &IMG_ITEM_HEART,             // Heart
 ];
 
 pub static ANIM_DRAGON_IDLE:  &[&'static Image; 1] = &[&IMG_DRAGON_IDLE_01];
@@ -80,4 +209,18 @@ pub static ANIM_DRAGON_WALK:  &[&'static Image; 3] = &[&IMG_DRAGON_WALK_01, &IMG
 pub static ANIM_DRAGON_JUMP:  &[&'static Image; 1] = &[&IMG_DRAGON_JUMP_01];
 pub static ANIM_DRAGON_FLY:   &[&'static Image; 2] = &[&IMG_DRAGON_FLY_01, &IMG_DRAGON_FLY_02];
 pub static ANIM_DRAGON_FALL:  &[&'static Image; 1] = &[&IMG_DRAGON_FALL_01];
-pub static ANIM_DRAGON_DEATH: &[&'static Image; 4] = &[&IMG_DRAGON_DEATH_01, &IMG_DRAGON_DEATH_02, &IMG_DRAGON_DEATH_03, &IMG_DRAGON_DEATH_04];
\ No newline at end of file
+pub static ANIM_DRAGON_DEATH: &[&'static Image; 4] = &[&IMG_DRAGON_DEATH_01, &IMG_DRAGON_DEATH_02, &IMG_DRAGON_DEATH_03, &IMG_DRAGON_DEATH_04];
+pub static ANIM_DRAGON_BREATH: &[&'static Image; 1] = &[&IMG_DRAGON_BREATH_01];
+pub static ANIM_DRAGON_WALL_SLIDE: &[&'static Image; 1] = &[&IMG_DRAGON_WALL_SLIDE_01];
+pub static ANIM_DRAGON_WALL_JUMP:  &[&'static Image; 1] = &[&IMG_DRAGON_WALL_JUMP_01];
+pub static ANIM_DRAGON_HURT:       &[&'static Image; 1] = &[&IMG_DRAGON_HURT_01];
+
+pub static ANIM_BOSS_FLOAT: &[&'static Image; 2] = &[&IMG_BOSS_FLOAT_01, &IMG_BOSS_FLOAT_02];
+pub static ANIM_BOSS_ANGRY: &[&'static Image; 2] = &[&IMG_BOSS_ANGRY_01, &IMG_BOSS_FLOAT_02];
+pub static ANIM_BOSS_STUN:  &[&'static Image; 2] = &[&IMG_BOSS_STUN_01, &IMG_BOSS_STUN_02];
+
+pub static ANIM_ENEMY_WALKER: &[&'static Image; 2] = &[&IMG_ENEMY_WALKER_01, &IMG_ENEMY_WALKER_02];
+pub static ANIM_ENEMY_FLYER:  &[&'static Image; 2] = &[&IMG_ENEMY_FLYER_01, &IMG_ENEMY_FLYER_02];
+pub static ANIM_BREATH:       &[&'static Image; 2] = &[&IMG_BREATH_01, &IMG_BREATH_02];
+pub static ANIM_WALL_BREAK:   &[&'static Image; 2] = &[&IMG_WALL_BREAK_01, &IMG_WALL_BREAK_02];
+pub static ANIM_TILE_CRUMBLE: &[&'static Image; 2] = &[&IMG_TILE_CRUMBLE_01, &IMG_TILE_CRUMBLE_02];
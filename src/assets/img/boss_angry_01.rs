@@ -0,0 +1,16 @@
+// boss_angry_01
+use crate::wasm4::*;
+use crate::utils::RawImage;
+
+const BOSS_ANGRY_01_WIDTH: u32 = 32;
+const BOSS_ANGRY_01_HEIGHT: u32 = 32;
+const BOSS_ANGRY_01_FLAGS: u32 = BLIT_2BPP;
+const BOSS_ANGRY_01: [u8; 256] = [ 0x01,0x00,0x00,0x00,0x00,0x00,0x00,0x40,0x00,0x40,0x00,0x00,0x00,0x00,0x01,0x00,0x00,0x50,0x00,0x00,0x00,0x00,0x05,0x00,0x00,0x15,0x00,0x00,0x00,0x00,0x54,0x00,0x40,0x15,0x40,0x00,0x00,0x01,0x54,0x01,0x50,0x05,0x50,0x00,0x40,0x05,0x50,0x05,0x50,0x05,0x54,0x55,0x95,0x55,0x50,0x05,0x14,0x01,0x59,0xaa,0xaa,0xa9,0x40,0x14,0x15,0x01,0x6a,0xaa,0xaa,0xaa,0x40,0x54,0x15,0x40,0x6a,0xaa,0xaa,0xaa,0x41,0x54,0x15,0x40,0x65,0xaa,0xaa,0x9a,0x41,0x54,0x15,0x51,0xaa,0x5b,0xf9,0x6a,0x95,0x54,0x15,0x56,0xaa,0xb5,0x57,0xaa,0xa5,0x54,0x15,0x5a,0xaa,0xfd,0x7f,0xea,0xa9,0x54,0x15,0x5a,0xab,0xfd,0x7f,0xfa,0xa9,0x54,0x15,0x46,0xaa,0xfd,0x7f,0xea,0xa9,0x54,0x05,0x46,0xaa,0xbd,0x7f,0xaa,0xa9,0x50,0x05,0x1a,0xaa,0xab,0xfa,0xaa,0xa9,0x50,0x04,0x06,0xaa,0xaa,0xaa,0xaa,0xa4,0x10,0x00,0x06,0xaa,0xaa,0xaa,0xaa,0xa4,0x00,0x00,0x06,0xaa,0xaa,0xaa,0xaa,0xa4,0x00,0x00,0x06,0xaa,0xaa,0xaa,0xaa,0xa4,0x00,0x00,0x01,0x95,0x55,0x55,0x55,0x90,0x00,0x00,0x01,0xba,0xeb,0xae,0xba,0x90,0x00,0x00,0x00,0x6a,0xea,0xae,0xaa,0x40,0x00,0x00,0x00,0x6a,0xaa,0xaa,0xaa,0x40,0x00,0x00,0x00,0x1a,0xaa,0xaa,0xa9,0x00,0x00,0x00,0x00,0x05,0xaa,0xaa,0x94,0x00,0x00,0x00,0x00,0x00,0x55,0x95,0x40,0x00,0x00,0x00,0x00,0x00,0x00,0x40,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00 ];
+
+pub const RAWIMG_BOSS_ANGRY_01: RawImage = RawImage {
+    width: BOSS_ANGRY_01_WIDTH,
+    height: BOSS_ANGRY_01_HEIGHT,
+    flags: BOSS_ANGRY_01_FLAGS,
+    data: &BOSS_ANGRY_01,
+};
+
@@ -0,0 +1,16 @@
+// boss_shot
+use crate::wasm4::*;
+use crate::utils::RawImage;
+
+const BOSS_SHOT_WIDTH: u32 = 8;
+const BOSS_SHOT_HEIGHT: u32 = 8;
+const BOSS_SHOT_FLAGS: u32 = BLIT_2BPP;
+const BOSS_SHOT: [u8; 16] = [ 0x05,0x50,0x1a,0xa4,0x6b,0xe9,0x6f,0xf9,0x6f,0xf9,0x6b,0xe9,0x1a,0xa4,0x05,0x50 ];
+
+pub const RAWIMG_BOSS_SHOT: RawImage = RawImage {
+    width: BOSS_SHOT_WIDTH,
+    height: BOSS_SHOT_HEIGHT,
+    flags: BOSS_SHOT_FLAGS,
+    data: &BOSS_SHOT,
+};
+
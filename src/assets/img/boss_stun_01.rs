@@ -0,0 +1,16 @@
+// boss_stun_01
+use crate::wasm4::*;
+use crate::utils::RawImage;
+
+const BOSS_STUN_01_WIDTH: u32 = 32;
+const BOSS_STUN_01_HEIGHT: u32 = 32;
+const BOSS_STUN_01_FLAGS: u32 = BLIT_2BPP;
+const BOSS_STUN_01: [u8; 256] = [ 0x01,0x00,0x00,0x00,0x00,0x00,0x00,0x40,0x00,0x40,0x00,0x00,0x00,0x00,0x01,0x00,0x00,0x50,0x00,0x00,0x00,0x00,0x05,0x00,0x00,0x15,0x00,0x00,0x00,0x00,0x54,0x00,0x00,0x15,0x40,0x00,0x00,0x01,0x54,0x00,0x00,0x05,0x50,0x00,0x40,0x05,0x50,0x00,0x00,0x05,0x54,0x55,0x95,0x55,0x50,0x00,0x00,0x01,0x59,0xaa,0xaa,0xa9,0x40,0x00,0x00,0x01,0x6a,0xaa,0xaa,0xaa,0x40,0x00,0x00,0x00,0x6a,0xaa,0xaa,0xaa,0x40,0x00,0x00,0x00,0x6a,0xaa,0xaa,0xaa,0x40,0x00,0x00,0x01,0xaa,0xaa,0xaa,0xaa,0x90,0x00,0x00,0x01,0xaa,0x9a,0xa9,0xaa,0x90,0x00,0x00,0x06,0xaa,0xa6,0xa6,0xaa,0xa4,0x00,0x00,0x06,0xaa,0xa9,0x9a,0xaa,0xa4,0x00,0x00,0x06,0xaa,0xaa,0x6a,0xaa,0xa4,0x00,0x00,0x06,0xaa,0xa9,0x9a,0xaa,0xa4,0x00,0x00,0x1a,0xaa,0xaa,0xaa,0xaa,0xa9,0x00,0x00,0x06,0xaa,0xaa,0xaa,0xaa,0xa4,0x00,0x00,0x46,0xaa,0xaa,0xaa,0xaa,0xa9,0x00,0x00,0x06,0xaa,0xaa,0xaa,0xaa,0xa4,0x00,0x01,0x06,0xaa,0xaa,0xaa,0xaa,0xa4,0x40,0x00,0x01,0x95,0x55,0x55,0x55,0x90,0x00,0x00,0x01,0xba,0xeb,0xae,0xba,0x90,0x00,0x00,0x00,0x6a,0xea,0xae,0xaa,0x40,0x00,0x00,0x00,0x6a,0xaa,0xaa,0xaa,0x40,0x00,0x00,0x00,0x1a,0xaa,0xaa,0xa9,0x00,0x00,0x00,0x00,0x05,0xaa,0xaa,0x94,0x00,0x00,0x00,0x00,0x00,0x55,0x95,0x40,0x00,0x00,0x00,0x00,0x00,0x00,0x40,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00 ];
+
+pub const RAWIMG_BOSS_STUN_01: RawImage = RawImage {
+    width: BOSS_STUN_01_WIDTH,
+    height: BOSS_STUN_01_HEIGHT,
+    flags: BOSS_STUN_01_FLAGS,
+    data: &BOSS_STUN_01,
+};
+
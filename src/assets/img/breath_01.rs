@@ -0,0 +1,16 @@
+// breath_01
+use crate::wasm4::*;
+use crate::utils::RawImage;
+
+const BREATH_01_WIDTH: u32 = 8;
+const BREATH_01_HEIGHT: u32 = 8;
+const BREATH_01_FLAGS: u32 = BLIT_2BPP;
+const BREATH_01: [u8; 16] = [ 0x00,0x00,0x01,0x40,0x16,0x94,0x6b,0xe9,0x6f,0xf9,0x1a,0xa4,0x05,0x50,0x00,0x00 ];
+
+pub const RAWIMG_BREATH_01: RawImage = RawImage {
+    width: BREATH_01_WIDTH,
+    height: BREATH_01_HEIGHT,
+    flags: BREATH_01_FLAGS,
+    data: &BREATH_01,
+};
+
@@ -0,0 +1,16 @@
+// breath_02
+use crate::wasm4::*;
+use crate::utils::RawImage;
+
+const BREATH_02_WIDTH: u32 = 8;
+const BREATH_02_HEIGHT: u32 = 8;
+const BREATH_02_FLAGS: u32 = BLIT_2BPP;
+const BREATH_02: [u8; 16] = [ 0x00,0x00,0x04,0x00,0x1a,0x50,0x6f,0xe4,0x6f,0xf9,0x6b,0xe4,0x15,0x40,0x00,0x00 ];
+
+pub const RAWIMG_BREATH_02: RawImage = RawImage {
+    width: BREATH_02_WIDTH,
+    height: BREATH_02_HEIGHT,
+    flags: BREATH_02_FLAGS,
+    data: &BREATH_02,
+};
+
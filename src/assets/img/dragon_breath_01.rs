@@ -0,0 +1,16 @@
+// dragon_breath_01
+use crate::wasm4::*;
+use crate::utils::RawImage;
+
+const DRAGON_BREATH_01_WIDTH: u32 = 16;
+const DRAGON_BREATH_01_HEIGHT: u32 = 16;
+const DRAGON_BREATH_01_FLAGS: u32 = BLIT_2BPP;
+const DRAGON_BREATH_01: [u8; 64] = [ 0xff,0x03,0xc3,0xff,0xff,0xc0,0xf0,0xff,0xff,0xf0,0x00,0x3f,0xff,0xf0,0x00,0x0f,0xff,0xc0,0x00,0x03,0xff,0xc0,0x20,0x83,0xff,0xc1,0x60,0x97,0xc0,0xf1,0x40,0xff,0xf0,0x3c,0xa8,0x1f,0xfc,0x03,0xaa,0xbf,0xcf,0x0e,0xaa,0xbf,0xc3,0xfa,0xaa,0xbf,0xf0,0xaa,0xaa,0xbf,0xfc,0x2a,0xaa,0xbf,0xff,0x00,0x5e,0xbf,0xff,0xff,0xad,0x7f ];
+
+pub const RAWIMG_DRAGON_BREATH_01: RawImage = RawImage {
+    width: DRAGON_BREATH_01_WIDTH,
+    height: DRAGON_BREATH_01_HEIGHT,
+    flags: DRAGON_BREATH_01_FLAGS,
+    data: &DRAGON_BREATH_01,
+};
+
@@ -0,0 +1,16 @@
+// dragon_hurt_01
+use crate::wasm4::*;
+use crate::utils::RawImage;
+
+const DRAGON_HURT_01_WIDTH: u32 = 16;
+const DRAGON_HURT_01_HEIGHT: u32 = 16;
+const DRAGON_HURT_01_FLAGS: u32 = BLIT_2BPP;
+const DRAGON_HURT_01: [u8; 64] = [ 0xff,0x03,0xc3,0xff,0xff,0xc0,0xf0,0xff,0xcf,0xf0,0x00,0x3f,0xc3,0xf0,0x00,0x0f,0xc0,0xc0,0x00,0x03,0xc0,0xc0,0x00,0x03,0xf0,0x00,0x00,0x03,0xcc,0x31,0x6a,0x9f,0xc3,0x0f,0xaa,0xbf,0xc0,0xfe,0xaa,0xbf,0xf0,0xaa,0xaa,0xaf,0xf0,0x2a,0xaa,0xaf,0xfc,0x0a,0xaa,0xab,0xff,0xf5,0xaa,0x5f,0xff,0xfd,0x5d,0x5f,0xff,0xff,0xaf,0xaf ];
+
+pub const RAWIMG_DRAGON_HURT_01: RawImage = RawImage {
+    width: DRAGON_HURT_01_WIDTH,
+    height: DRAGON_HURT_01_HEIGHT,
+    flags: DRAGON_HURT_01_FLAGS,
+    data: &DRAGON_HURT_01,
+};
+
@@ -0,0 +1,16 @@
+// dragon_wall_jump_01
+use crate::wasm4::*;
+use crate::utils::RawImage;
+
+const DRAGON_WALL_JUMP_01_WIDTH: u32 = 16;
+const DRAGON_WALL_JUMP_01_HEIGHT: u32 = 16;
+const DRAGON_WALL_JUMP_01_FLAGS: u32 = BLIT_2BPP;
+const DRAGON_WALL_JUMP_01: [u8; 64] = [ 0x3f,0x03,0xc3,0xff,0x0f,0xc0,0xf0,0xff,0xc3,0xf0,0x00,0x3f,0xf0,0xf0,0x00,0x0f,0xfc,0x00,0x20,0x83,0xff,0x00,0x20,0x83,0xff,0xc1,0x40,0x17,0xff,0xf1,0x6a,0x9f,0xff,0xf0,0xaa,0xbf,0xff,0xfe,0xaa,0xbf,0xff,0xfa,0xaa,0xbf,0xff,0xea,0xaa,0xbf,0xff,0xaa,0xaa,0xbf,0xfc,0x2a,0xab,0xff,0xf0,0x55,0x7f,0xff,0xff,0xaf,0xff,0xff ];
+
+pub const RAWIMG_DRAGON_WALL_JUMP_01: RawImage = RawImage {
+    width: DRAGON_WALL_JUMP_01_WIDTH,
+    height: DRAGON_WALL_JUMP_01_HEIGHT,
+    flags: DRAGON_WALL_JUMP_01_FLAGS,
+    data: &DRAGON_WALL_JUMP_01,
+};
+
@@ -0,0 +1,16 @@
+// dragon_wall_slide_01
+use crate::wasm4::*;
+use crate::utils::RawImage;
+
+const DRAGON_WALL_SLIDE_01_WIDTH: u32 = 16;
+const DRAGON_WALL_SLIDE_01_HEIGHT: u32 = 16;
+const DRAGON_WALL_SLIDE_01_FLAGS: u32 = BLIT_2BPP;
+const DRAGON_WALL_SLIDE_01: [u8; 64] = [ 0xff,0x03,0xc3,0xff,0xff,0xc0,0xf0,0xff,0xff,0xf0,0x00,0x3f,0xff,0xf0,0x00,0x0f,0xff,0xc0,0x20,0x83,0xff,0x00,0x20,0x83,0xfc,0xc1,0x40,0x17,0xf0,0x31,0x6a,0x9d,0xff,0x0c,0xaa,0xbd,0xff,0xc3,0xaa,0xbd,0xff,0xfe,0xaa,0xbf,0xff,0xfa,0xaa,0xbd,0xff,0xea,0xaa,0xbd,0xff,0xc2,0xaa,0xbf,0xff,0xf0,0x55,0x5f,0xff,0xff,0xaf,0xaf ];
+
+pub const RAWIMG_DRAGON_WALL_SLIDE_01: RawImage = RawImage {
+    width: DRAGON_WALL_SLIDE_01_WIDTH,
+    height: DRAGON_WALL_SLIDE_01_HEIGHT,
+    flags: DRAGON_WALL_SLIDE_01_FLAGS,
+    data: &DRAGON_WALL_SLIDE_01,
+};
+
@@ -0,0 +1,16 @@
+// enemy_flyer_01
+use crate::wasm4::*;
+use crate::utils::RawImage;
+
+const ENEMY_FLYER_01_WIDTH: u32 = 16;
+const ENEMY_FLYER_01_HEIGHT: u32 = 16;
+const ENEMY_FLYER_01_FLAGS: u32 = BLIT_2BPP;
+const ENEMY_FLYER_01: [u8; 64] = [ 0x00,0x00,0x00,0x00,0x40,0x00,0x00,0x01,0x50,0x00,0x00,0x05,0x64,0x01,0x40,0x19,0x69,0x06,0x90,0x69,0x6a,0x5a,0xa5,0xaa,0x1a,0xaa,0xaa,0xa9,0x1a,0xae,0xba,0xa4,0x06,0xa7,0x9e,0x90,0x01,0x6a,0xa9,0x40,0x00,0x1b,0xba,0x40,0x00,0x05,0x55,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00 ];
+
+pub const RAWIMG_ENEMY_FLYER_01: RawImage = RawImage {
+    width: ENEMY_FLYER_01_WIDTH,
+    height: ENEMY_FLYER_01_HEIGHT,
+    flags: ENEMY_FLYER_01_FLAGS,
+    data: &ENEMY_FLYER_01,
+};
+
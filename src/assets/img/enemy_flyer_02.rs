@@ -0,0 +1,16 @@
+// enemy_flyer_02
+use crate::wasm4::*;
+use crate::utils::RawImage;
+
+const ENEMY_FLYER_02_WIDTH: u32 = 16;
+const ENEMY_FLYER_02_HEIGHT: u32 = 16;
+const ENEMY_FLYER_02_FLAGS: u32 = BLIT_2BPP;
+const ENEMY_FLYER_02: [u8; 64] = [ 0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x01,0x40,0x00,0x00,0x06,0x90,0x00,0x00,0x1a,0xa4,0x00,0x05,0x6b,0xae,0x40,0x1a,0x9e,0x7a,0x40,0x6a,0xaa,0xaa,0xa4,0x6a,0x5a,0xa9,0x6a,0x69,0x06,0xee,0x91,0x64,0x01,0x55,0x41,0x50,0x00,0x00,0x00,0x40,0x00,0x00,0x00,0x00,0x00,0x00,0x00 ];
+
+pub const RAWIMG_ENEMY_FLYER_02: RawImage = RawImage {
+    width: ENEMY_FLYER_02_WIDTH,
+    height: ENEMY_FLYER_02_HEIGHT,
+    flags: ENEMY_FLYER_02_FLAGS,
+    data: &ENEMY_FLYER_02,
+};
+
@@ -0,0 +1,16 @@
+// enemy_shot_01
+use crate::wasm4::*;
+use crate::utils::RawImage;
+
+const ENEMY_SHOT_01_WIDTH: u32 = 8;
+const ENEMY_SHOT_01_HEIGHT: u32 = 8;
+const ENEMY_SHOT_01_FLAGS: u32 = BLIT_1BPP;
+const ENEMY_SHOT_01: [u8; 8] = [ 0x00,0x3c,0x7e,0x7e,0x7e,0x7e,0x3c,0x00 ];
+
+pub const RAWIMG_ENEMY_SHOT_01: RawImage = RawImage {
+    width: ENEMY_SHOT_01_WIDTH,
+    height: ENEMY_SHOT_01_HEIGHT,
+    flags: ENEMY_SHOT_01_FLAGS,
+    data: &ENEMY_SHOT_01,
+};
+
@@ -0,0 +1,16 @@
+// enemy_turret_01
+use crate::wasm4::*;
+use crate::utils::RawImage;
+
+const ENEMY_TURRET_01_WIDTH: u32 = 16;
+const ENEMY_TURRET_01_HEIGHT: u32 = 16;
+const ENEMY_TURRET_01_FLAGS: u32 = BLIT_2BPP;
+const ENEMY_TURRET_01: [u8; 64] = [ 0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x55,0x55,0x00,0x01,0xaa,0xaa,0x40,0x06,0xbf,0xfe,0x90,0x06,0xd5,0x57,0x95,0x06,0xda,0xa7,0xa9,0x06,0xdb,0xe7,0xfd,0x06,0xda,0xa7,0xa9,0x06,0xd5,0x57,0x95,0x06,0xbf,0xfe,0x90,0x06,0xaa,0xaa,0x90,0x05,0x55,0x55,0x50,0x1a,0xaa,0xaa,0xa4,0x15,0x55,0x55,0x54,0x00,0x00,0x00,0x00 ];
+
+pub const RAWIMG_ENEMY_TURRET_01: RawImage = RawImage {
+    width: ENEMY_TURRET_01_WIDTH,
+    height: ENEMY_TURRET_01_HEIGHT,
+    flags: ENEMY_TURRET_01_FLAGS,
+    data: &ENEMY_TURRET_01,
+};
+
@@ -0,0 +1,16 @@
+// enemy_walker_01
+use crate::wasm4::*;
+use crate::utils::RawImage;
+
+const ENEMY_WALKER_01_WIDTH: u32 = 16;
+const ENEMY_WALKER_01_HEIGHT: u32 = 16;
+const ENEMY_WALKER_01_FLAGS: u32 = BLIT_2BPP;
+const ENEMY_WALKER_01: [u8; 64] = [ 0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x15,0x54,0x00,0x01,0x6a,0xa9,0x40,0x06,0xaa,0xaa,0x90,0x06,0xab,0xab,0xd0,0x1a,0xa9,0xea,0x74,0x1a,0xaa,0xaa,0xa4,0x1a,0xaa,0xaa,0xa4,0x05,0x55,0x55,0x50,0x04,0x41,0x10,0x40,0x10,0x41,0x04,0x10,0x10,0x10,0x41,0x00,0x00,0x00,0x00,0x00 ];
+
+pub const RAWIMG_ENEMY_WALKER_01: RawImage = RawImage {
+    width: ENEMY_WALKER_01_WIDTH,
+    height: ENEMY_WALKER_01_HEIGHT,
+    flags: ENEMY_WALKER_01_FLAGS,
+    data: &ENEMY_WALKER_01,
+};
+
@@ -0,0 +1,16 @@
+// font
+use crate::wasm4::*;
+use crate::utils::RawImage;
+
+const FONT_WIDTH: u32 = 128;
+const FONT_HEIGHT: u32 = 112;
+const FONT_FLAGS: u32 = BLIT_1BPP;
+const FONT: [u8; 1792] = [ 0x00,0x80,0xa0,0x00,0x20,0xc0,0x60,0x80,0x20,0x80,0x00,0x00,0x00,0x00,0x00,0x08,0x00,0x80,0xa0,0x50,0x78,0xc8,0x90,0x80,0x40,0x40,0x20,0x20,0x00,0x00,0x00,0x10,0x00,0x80,0x00,0xf8,0xa0,0x10,0xa0,0x00,0x80,0x20,0xa8,0x20,0x00,0x00,0x00,0x10,0x00,0x80,0x00,0x50,0x70,0x20,0x40,0x00,0x80,0x20,0x70,0xf8,0x00,0xf0,0x00,0x20,0x00,0x80,0x00,0xf8,0x28,0x40,0xa8,0x00,0x80,0x20,0xa8,0x20,0x00,0x00,0x00,0x40,0x00,0x00,0x00,0x50,0xf0,0x98,0x90,0x00,0x40,0x40,0x20,0x20,0x40,0x00,0x00,0x40,0x00,0x80,0x00,0x00,0x20,0x18,0x68,0x00,0x20,0x80,0x00,0x00,0x40,0x00,0x80,0x80,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x80,0x00,0x00,0x00,0x70,0x40,0x70,0xf8,0x10,0xf8,0x30,0xf8,0x70,0x70,0x00,0x00,0x10,0x00,0x80,0x70,0x88,0xc0,0x88,0x10,0x30,0x80,0x40,0x08,0x88,0x88,0x00,0x00,0x20,0x00,0x40,0x88,0x98,0x40,0x08,0x20,0x50,0xf0,0x80,0x10,0x88,0x88,0x80,0x40,0x40,0xf0,0x20,0x08,0xa8,0x40,0x10,0x10,0x90,0x08,0xf0,0x20,0x70,0x78,0x00,0x00,0x80,0x00,0x10,0x10,0xc8,0x40,0x20,0x08,0xf8,0x08,0x88,0x40,0x88,0x08,0x00,0x00,0x40,0xf0,0x20,0x20,0x88,0x40,0x40,0x88,0x10,0x88,0x88,0x40,0x88,0x10,0x80,0x40,0x20,0x00,0x40,0x00,0x70,0xe0,0xf8,0x70,0x10,0x70,0x70,0x40,0x70,0x60,0x00,0x40,0x10,0x00,0x80,0x20,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x80,0x00,0x00,0x00,0x00,0x70,0x70,0xf0,0x70,0xf0,0xf8,0xf8,0x70,0x88,0xe0,0x38,0x88,0x80,0x88,0x88,0x70,0x88,0x88,0x88,0x88,0x88,0x80,0x80,0x88,0x88,0x40,0x10,0x90,0x80,0xd8,0x88,0x88,0xb8,0x88,0x88,0x80,0x88,0x80,0x80,0x80,0x88,0x40,0x10,0xa0,0x80,0xa8,0xc8,0x88,0xa8,0xf8,0xf0,0x80,0x88,0xf0,0xf0,0xb8,0xf8,0x40,0x10,0xc0,0x80,0xa8,0xa8,0x88,0xb8,0x88,0x88,0x80,0x88,0x80,0x80,0x88,0x88,0x40,0x10,0xa0,0x80,0x88,0x98,0x88,0x80,0x88,0x88,0x88,0x88,0x80,0x80,0x88,0x88,0x40,0x90,0x90,0x80,0x88,0x88,0x88,0x78,0x88,0xf0,0x70,0xf0,0xf8,0x80,0x78,0x88,0xe0,0x60,0x88,0xf8,0x88,0x88,0x70,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0xf0,0x70,0xf0,0x78,0xf8,0x88,0x88,0x88,0x88,0x88,0xf8,0xe0,0x80,0xe0,0x20,0x00,0x88,0x88,0x88,0x80,0x20,0x88,0x88,0x88,0x88,0x88,0x08,0x80,0x40,0x20,0x50,0x00,0x88,0x88,0x88,0x80,0x20,0x88,0x88,0x88,0x50,0x50,0x10,0x80,0x40,0x20,0x88,0x00,0xf0,0x88,0xf0,0x70,0x20,0x88,0x88,0xa8,0x20,0x20,0x20,0x80,0x20,0x20,0x00,0x00,0x80,0xa8,0xa0,0x08,0x20,0x88,0x88,0xa8,0x50,0x20,0x40,0x80,0x10,0x20,0x00,0x00,0x80,0x90,0x90,0x08,0x20,0x88,0x50,0xa8,0x88,0x20,0x80,0x80,0x10,0x20,0x00,0x00,0x80,0x68,0x88,0xf0,0x20,0x70,0x20,0x50,0x88,0x20,0xf8,0xe0,0x08,0xe0,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0xf8,0x80,0x00,0x80,0x00,0x08,0x00,0x30,0x00,0x80,0x80,0x20,0x80,0x80,0x00,0x00,0x00,0x40,0x00,0x80,0x00,0x08,0x00,0x40,0x00,0x80,0x00,0x00,0x80,0x80,0x00,0x00,0x00,0x00,0x70,0xf0,0x70,0x78,0x70,0xf0,0x78,0xf0,0x80,0x20,0x90,0x80,0xd0,0xf0,0x70,0x00,0x08,0x88,0x80,0x88,0x88,0x40,0x88,0x88,0x80,0x20,0xa0,0x80,0xa8,0x88,0x88,0x00,0x78,0x88,0x80,0x88,0xf8,0x40,0x88,0x88,0x80,0x20,0xc0,0x80,0xa8,0x88,0x88,0x00,0x88,0x88,0x80,0x88,0x80,0x40,0x78,0x88,0x80,0x20,0xa0,0x80,0xa8,0x88,0x88,0x00,0x78,0xf0,0x70,0x78,0x70,0x40,0x08,0x88,0x80,0xa0,0x90,0x40,0xa8,0x88,0x70,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x70,0x00,0x00,0x40,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x40,0x00,0x00,0x00,0x00,0x00,0x00,0x20,0x80,0x80,0x00,0x00,0x00,0x00,0x00,0x00,0x40,0x00,0x00,0x00,0x00,0x00,0x00,0x40,0x80,0x40,0x00,0x00,0xf0,0x78,0xb0,0x70,0xf0,0x88,0x88,0x88,0x88,0x88,0xf8,0x40,0x80,0x40,0x40,0x20,0x88,0x88,0xc0,0x80,0x40,0x88,0x88,0x88,0x50,0x88,0x10,0x80,0x80,0x20,0xa8,0x78,0x88,0x88,0x80,0x60,0x40,0x88,0x88,0xa8,0x20,0x88,0x20,0x40,0x80,0x40,0x10,0x54,0xf0,0x78,0x80,0x10,0x40,0x88,0x50,0xa8,0x50,0x78,0x40,0x40,0x80,0x40,0x00,0x54,0x80,0x08,0x80,0xe0,0x30,0x78,0x20,0x50,0x88,0x08,0xf8,0x20,0x80,0x80,0x00,0x28,0x80,0x08,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x70,0x00,0x00,0x00,0x00,0x00,0x00,0x10,0x00,0x00,0x00,0x30,0x00,0x30,0x00,0x40,0x20,0x20,0x08,0x88,0x78,0x20,0x80,0x7c,0x00,0x88,0x00,0x00,0x00,0x00,0x00,0xf4,0xfa,0xf8,0x10,0xbe,0x04,0xfc,0x80,0x10,0x00,0x84,0x20,0x78,0x20,0x78,0x40,0x40,0x2a,0x10,0x20,0x88,0x00,0x10,0x80,0x3c,0x48,0x84,0x70,0x84,0x70,0x10,0xf4,0x78,0x48,0xfc,0x40,0x88,0x00,0x18,0x80,0x52,0x44,0x80,0x08,0x04,0x20,0x20,0x58,0xc4,0x48,0x70,0x20,0x88,0x40,0x78,0x80,0x52,0x40,0x90,0x10,0x08,0x50,0x50,0xc8,0x44,0x90,0x88,0x10,0x90,0x80,0x80,0x84,0x24,0x30,0x60,0x60,0x30,0x98,0x8e,0x50,0x58,0xa0,0x70,0x08,0xa0,0x7c,0x78,0x78,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x10,0x48,0x78,0x40,0x20,0x00,0x00,0xfe,0x40,0x40,0xbc,0x28,0x40,0x78,0x90,0xe8,0xfe,0xfe,0x10,0xf0,0xfc,0x00,0x78,0x08,0x40,0xe4,0x80,0x7c,0x58,0x94,0xbc,0x4c,0x30,0x48,0x20,0x4c,0x20,0x00,0x84,0x10,0x4c,0x48,0x80,0xa2,0xe4,0x92,0x90,0x84,0x50,0x48,0xfe,0x40,0x58,0x70,0x04,0x20,0x70,0x88,0x80,0xa2,0x44,0xa2,0x90,0x82,0x30,0x58,0x20,0x90,0x64,0x88,0x04,0x20,0x80,0x9c,0xa0,0xae,0xc4,0xa2,0xb8,0x82,0x10,0x40,0x20,0x90,0x04,0x08,0x08,0x20,0x80,0x2a,0xa0,0x52,0xce,0x44,0xd4,0x44,0x20,0x3c,0x1c,0x8c,0x70,0x30,0x30,0x1c,0x78,0x10,0x9c,0x0c,0x4c,0x00,0x98,0x38,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x20,0x00,0xbc,0x10,0x70,0x40,0x48,0x20,0x00,0x20,0x00,0x50,0x00,0x10,0x60,0x88,0x10,0x20,0x90,0xfe,0x10,0xf4,0x5c,0x78,0x00,0x2c,0x00,0xbc,0x00,0x1c,0x10,0x88,0x10,0x50,0xb8,0x10,0x24,0x42,0xfa,0x20,0x40,0xfe,0x50,0xaa,0x10,0x10,0x40,0x88,0x28,0x88,0x90,0xfe,0x7a,0x60,0xa2,0x78,0x78,0x12,0xb8,0xaa,0x18,0x10,0x80,0x88,0xa4,0x04,0x90,0x10,0xa6,0xa0,0xa2,0x24,0xc8,0x14,0xa8,0x9c,0x10,0x70,0xb8,0x48,0xa6,0x02,0xb8,0x78,0x48,0x64,0xc4,0x24,0x58,0x08,0x70,0x88,0x70,0x98,0xc4,0x10,0x30,0x00,0x94,0x96,0x10,0x3c,0x48,0x18,0x20,0x08,0x20,0x10,0xb0,0x64,0x18,0x20,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x78,0x40,0x78,0x40,0x40,0x10,0x00,0xfe,0x00,0x04,0x00,0x10,0x00,0x00,0x00,0x08,0x10,0x58,0x10,0x58,0xf0,0x10,0x00,0x04,0x00,0x08,0x00,0xfe,0x00,0x7c,0x00,0xfe,0x20,0xe4,0x20,0xe4,0x40,0x20,0xf8,0x18,0x08,0x10,0x20,0x82,0x00,0x10,0x10,0x18,0x78,0x44,0x78,0x44,0x94,0x20,0x08,0x10,0x10,0x60,0xf8,0x04,0x70,0x10,0xf8,0x28,0x84,0xc4,0x84,0xc4,0x68,0x50,0x30,0x10,0x60,0xa0,0x88,0x08,0x20,0x10,0x30,0x48,0x34,0xc4,0x04,0xc8,0x20,0x48,0x20,0x20,0x20,0x20,0x10,0x10,0x20,0x10,0x50,0x88,0x38,0x46,0x70,0x50,0x1c,0x8e,0x40,0x40,0x20,0x20,0x60,0x60,0xf8,0xfe,0xb0,0x30,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x20,0x20,0x20,0x40,0xfc,0x48,0xc0,0xfc,0x40,0x84,0x20,0x1c,0x00,0x00,0x7c,0x40,0xfc,0xf8,0x7c,0x7e,0x04,0xfe,0x02,0x04,0x4e,0x84,0x7c,0xf0,0x00,0xa4,0x00,0x40,0x24,0x20,0x84,0x88,0x04,0x48,0xc2,0x08,0x7a,0x44,0x84,0x10,0x00,0xa4,0xfe,0x60,0x24,0xfe,0x08,0x08,0x04,0x08,0x04,0x10,0x44,0x08,0x68,0xfe,0xa8,0x44,0x10,0x58,0x44,0x10,0x10,0x10,0x04,0x10,0x08,0x28,0x40,0x08,0x10,0x10,0xa8,0x08,0x10,0x44,0x44,0x10,0x20,0x20,0x04,0x20,0x10,0x44,0x40,0x10,0x20,0x20,0x10,0x10,0x20,0x40,0x98,0x10,0x40,0x40,0xfc,0x40,0xe0,0x82,0x3c,0x60,0x40,0x40,0x60,0x60,0x40,0x40,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x10,0x00,0xfc,0x10,0x04,0x28,0x80,0xfe,0x00,0x10,0xfe,0x70,0x10,0x04,0x7c,0x00,0x10,0x7c,0x04,0x7c,0x04,0x24,0x8c,0x02,0x20,0xfe,0x02,0x0c,0x10,0x04,0x10,0x00,0xfe,0x00,0x48,0x08,0x08,0x44,0xe0,0x04,0x50,0x10,0x04,0x00,0x20,0x48,0x10,0x40,0x10,0x00,0x30,0x10,0x08,0x42,0x80,0x04,0x88,0x54,0x48,0x70,0x20,0x30,0xfe,0x78,0x10,0x00,0x30,0x3c,0x10,0x82,0x80,0x08,0x04,0x92,0x30,0x0c,0x48,0x30,0x10,0xc8,0x20,0xfe,0x48,0x54,0x20,0x80,0x80,0x10,0x02,0x10,0x10,0x00,0x44,0x48,0x10,0x50,0x40,0x00,0x80,0x90,0xc0,0x00,0x7c,0x60,0x00,0x30,0x08,0x7c,0xfe,0x80,0x0e,0x40,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x40,0x00,0x00,0x00,0xfc,0x7c,0x88,0x28,0x80,0xfc,0xfe,0xfe,0xc0,0x00,0x00,0xe0,0x4c,0x00,0x78,0x00,0x04,0x00,0x88,0x28,0x80,0x84,0x82,0x02,0x02,0x00,0x00,0x80,0xfc,0x00,0x08,0xf0,0x04,0xfe,0x88,0x28,0x80,0x84,0x82,0x7c,0x04,0x00,0x00,0x80,0x48,0x70,0x08,0x10,0xfc,0x02,0x88,0x28,0x82,0x84,0x04,0x04,0x04,0x00,0x00,0x80,0x20,0x10,0x08,0xf0,0x04,0x04,0x08,0x4a,0x84,0x84,0x08,0x08,0x08,0x80,0x40,0x80,0x20,0x10,0x08,0x10,0x04,0x08,0x10,0x4c,0x98,0x84,0x10,0x10,0x10,0x40,0xa0,0x00,0x20,0xf8,0xfe,0xf0,0xfc,0x60,0x60,0x88,0xe0,0xfc,0x60,0x60,0xe0,0x20,0x40,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0xa0,0x40,0x7c,0x7c,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0xa0,0xa0,0x82,0x82,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x10,0x00,0x00,0x00,0x40,0xaa,0xba,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x10,0x40,0xfc,0x00,0x00,0x92,0x92,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x10,0x00,0x00,0x00,0x00,0xaa,0xba,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x10,0x00,0x00,0x00,0x00,0x82,0x82,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x70,0x00,0x00,0x00,0x00,0x7c,0x7c,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00 ];
+
+pub const RAWIMG_FONT: RawImage = RawImage {
+    width: FONT_WIDTH,
+    height: FONT_HEIGHT,
+    flags: FONT_FLAGS,
+    data: &FONT,
+};
+
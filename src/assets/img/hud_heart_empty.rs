@@ -0,0 +1,16 @@
+// hud_heart_empty
+use crate::wasm4::*;
+use crate::utils::RawImage;
+
+const HUD_HEART_EMPTY_WIDTH: u32 = 8;
+const HUD_HEART_EMPTY_HEIGHT: u32 = 8;
+const HUD_HEART_EMPTY_FLAGS: u32 = BLIT_2BPP;
+const HUD_HEART_EMPTY: [u8; 16] = [ 0x14,0x50,0x41,0x04,0x40,0x04,0x40,0x04,0x10,0x10,0x04,0x40,0x01,0x00,0x00,0x00 ];
+
+pub const RAWIMG_HUD_HEART_EMPTY: RawImage = RawImage {
+    width: HUD_HEART_EMPTY_WIDTH,
+    height: HUD_HEART_EMPTY_HEIGHT,
+    flags: HUD_HEART_EMPTY_FLAGS,
+    data: &HUD_HEART_EMPTY,
+};
+
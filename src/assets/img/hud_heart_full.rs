@@ -0,0 +1,16 @@
+// hud_heart_full
+use crate::wasm4::*;
+use crate::utils::RawImage;
+
+const HUD_HEART_FULL_WIDTH: u32 = 8;
+const HUD_HEART_FULL_HEIGHT: u32 = 8;
+const HUD_HEART_FULL_FLAGS: u32 = BLIT_2BPP;
+const HUD_HEART_FULL: [u8; 16] = [ 0x14,0x50,0x79,0xf4,0x7f,0xf4,0x7f,0xf4,0x1f,0xd0,0x07,0x40,0x01,0x00,0x00,0x00 ];
+
+pub const RAWIMG_HUD_HEART_FULL: RawImage = RawImage {
+    width: HUD_HEART_FULL_WIDTH,
+    height: HUD_HEART_FULL_HEIGHT,
+    flags: HUD_HEART_FULL_FLAGS,
+    data: &HUD_HEART_FULL,
+};
+
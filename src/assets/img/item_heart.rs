@@ -0,0 +1,16 @@
+// item_heart
+use crate::wasm4::*;
+use crate::utils::RawImage;
+
+const ITEM_HEART_WIDTH: u32 = 16;
+const ITEM_HEART_HEIGHT: u32 = 16;
+const ITEM_HEART_FLAGS: u32 = BLIT_2BPP;
+const ITEM_HEART: [u8; 64] = [ 0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x01,0x54,0x15,0x40,0x07,0xfd,0x7f,0xd0,0x1f,0xaf,0xff,0xf4,0x1e,0xbf,0xff,0xf4,0x1e,0xff,0xff,0xf4,0x1f,0xff,0xff,0xf4,0x07,0xff,0xff,0xd0,0x01,0xff,0xff,0x40,0x00,0x7f,0xfd,0x00,0x00,0x1f,0xf4,0x00,0x00,0x07,0xd0,0x00,0x00,0x01,0x40,0x00,0x00,0x00,0x00,0x00 ];
+
+pub const RAWIMG_ITEM_HEART: RawImage = RawImage {
+    width: ITEM_HEART_WIDTH,
+    height: ITEM_HEART_HEIGHT,
+    flags: ITEM_HEART_FLAGS,
+    data: &ITEM_HEART,
+};
+
@@ -0,0 +1,16 @@
+// platform_01
+use crate::wasm4::*;
+use crate::utils::RawImage;
+
+const PLATFORM_01_WIDTH: u32 = 16;
+const PLATFORM_01_HEIGHT: u32 = 8;
+const PLATFORM_01_FLAGS: u32 = BLIT_2BPP;
+const PLATFORM_01: [u8; 32] = [ 0x55,0x55,0x55,0x55,0x7f,0xff,0xff,0xfd,0x7a,0xeb,0xae,0xb9,0x6a,0xaa,0xaa,0xa9,0x55,0x55,0x55,0x55,0x10,0x40,0x01,0x04,0x14,0x00,0x00,0x50,0x04,0x00,0x00,0x10 ];
+
+pub const RAWIMG_PLATFORM_01: RawImage = RawImage {
+    width: PLATFORM_01_WIDTH,
+    height: PLATFORM_01_HEIGHT,
+    flags: PLATFORM_01_FLAGS,
+    data: &PLATFORM_01,
+};
+
@@ -0,0 +1,16 @@
+// tile_checkpoint_off
+use crate::wasm4::*;
+use crate::utils::RawImage;
+
+const TILE_CHECKPOINT_OFF_WIDTH: u32 = 16;
+const TILE_CHECKPOINT_OFF_HEIGHT: u32 = 16;
+const TILE_CHECKPOINT_OFF_FLAGS: u32 = BLIT_2BPP;
+const TILE_CHECKPOINT_OFF: [u8; 64] = [ 0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x01,0x40,0x00,0x00,0x01,0x40,0x00,0x00,0x01,0x40,0x00,0x00,0x01,0x40,0x00,0x00,0x01,0x40,0x00,0x00,0x01,0x6a,0xa0,0x00,0x01,0x6a,0xa0,0x00,0x01,0x6a,0xa8,0x00,0x01,0x6a,0xaa,0x00,0x01,0x41,0x58,0x00,0x01,0x40,0x00,0x00,0x05,0x55,0x00,0x00,0x15,0x55,0x40,0x00 ];
+
+pub const RAWIMG_TILE_CHECKPOINT_OFF: RawImage = RawImage {
+    width: TILE_CHECKPOINT_OFF_WIDTH,
+    height: TILE_CHECKPOINT_OFF_HEIGHT,
+    flags: TILE_CHECKPOINT_OFF_FLAGS,
+    data: &TILE_CHECKPOINT_OFF,
+};
+
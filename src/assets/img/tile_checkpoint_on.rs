@@ -0,0 +1,16 @@
+// tile_checkpoint_on
+use crate::wasm4::*;
+use crate::utils::RawImage;
+
+const TILE_CHECKPOINT_ON_WIDTH: u32 = 16;
+const TILE_CHECKPOINT_ON_HEIGHT: u32 = 16;
+const TILE_CHECKPOINT_ON_FLAGS: u32 = BLIT_2BPP;
+const TILE_CHECKPOINT_ON: [u8; 64] = [ 0x01,0x40,0x00,0x00,0x01,0x7f,0xfc,0x00,0x01,0x7f,0xff,0x00,0x01,0x7f,0xff,0xc0,0x01,0x7f,0xff,0x00,0x01,0x7f,0xfc,0x00,0x01,0x55,0x40,0x00,0x01,0x40,0x00,0x00,0x01,0x40,0x00,0x00,0x01,0x40,0x00,0x00,0x01,0x40,0x00,0x00,0x01,0x40,0x00,0x00,0x01,0x40,0x00,0x00,0x01,0x40,0x00,0x00,0x05,0x55,0x00,0x00,0x15,0x55,0x40,0x00 ];
+
+pub const RAWIMG_TILE_CHECKPOINT_ON: RawImage = RawImage {
+    width: TILE_CHECKPOINT_ON_WIDTH,
+    height: TILE_CHECKPOINT_ON_HEIGHT,
+    flags: TILE_CHECKPOINT_ON_FLAGS,
+    data: &TILE_CHECKPOINT_ON,
+};
+
@@ -0,0 +1,16 @@
+// tile_conveyor_left
+use crate::wasm4::*;
+use crate::utils::RawImage;
+
+const TILE_CONVEYOR_LEFT_WIDTH: u32 = 16;
+const TILE_CONVEYOR_LEFT_HEIGHT: u32 = 16;
+const TILE_CONVEYOR_LEFT_FLAGS: u32 = BLIT_1BPP;
+const TILE_CONVEYOR_LEFT: [u8; 32] = [ 0xff,0xff,0x80,0x01,0x88,0x21,0x98,0x61,0xbf,0xfd,0x98,0x61,0x88,0x21,0x80,0x01,0xff,0xff,0xaa,0xab,0xd5,0x55,0xff,0xff,0x80,0x01,0x80,0x01,0x80,0x01,0xff,0xff ];
+
+pub const RAWIMG_TILE_CONVEYOR_LEFT: RawImage = RawImage {
+    width: TILE_CONVEYOR_LEFT_WIDTH,
+    height: TILE_CONVEYOR_LEFT_HEIGHT,
+    flags: TILE_CONVEYOR_LEFT_FLAGS,
+    data: &TILE_CONVEYOR_LEFT,
+};
+
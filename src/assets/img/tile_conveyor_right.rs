@@ -0,0 +1,16 @@
+// tile_conveyor_right
+use crate::wasm4::*;
+use crate::utils::RawImage;
+
+const TILE_CONVEYOR_RIGHT_WIDTH: u32 = 16;
+const TILE_CONVEYOR_RIGHT_HEIGHT: u32 = 16;
+const TILE_CONVEYOR_RIGHT_FLAGS: u32 = BLIT_1BPP;
+const TILE_CONVEYOR_RIGHT: [u8; 32] = [ 0xff,0xff,0x80,0x01,0x84,0x11,0x86,0x19,0xbf,0xfd,0x86,0x19,0x84,0x11,0x80,0x01,0xff,0xff,0xd5,0x55,0xaa,0xab,0xff,0xff,0x80,0x01,0x80,0x01,0x80,0x01,0xff,0xff ];
+
+pub const RAWIMG_TILE_CONVEYOR_RIGHT: RawImage = RawImage {
+    width: TILE_CONVEYOR_RIGHT_WIDTH,
+    height: TILE_CONVEYOR_RIGHT_HEIGHT,
+    flags: TILE_CONVEYOR_RIGHT_FLAGS,
+    data: &TILE_CONVEYOR_RIGHT,
+};
+
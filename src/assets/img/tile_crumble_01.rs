@@ -0,0 +1,16 @@
+// tile_crumble_01
+use crate::wasm4::*;
+use crate::utils::RawImage;
+
+const TILE_CRUMBLE_01_WIDTH: u32 = 16;
+const TILE_CRUMBLE_01_HEIGHT: u32 = 16;
+const TILE_CRUMBLE_01_FLAGS: u32 = BLIT_1BPP;
+const TILE_CRUMBLE_01: [u8; 32] = [ 0xff,0xff,0x80,0x01,0x90,0x11,0x80,0x01,0x80,0x81,0x80,0x01,0x84,0x11,0xff,0xff,0x80,0x01,0xa0,0x41,0x80,0x01,0x82,0x05,0x80,0x01,0x90,0x11,0x80,0x01,0xff,0xff ];
+
+pub const RAWIMG_TILE_CRUMBLE_01: RawImage = RawImage {
+    width: TILE_CRUMBLE_01_WIDTH,
+    height: TILE_CRUMBLE_01_HEIGHT,
+    flags: TILE_CRUMBLE_01_FLAGS,
+    data: &TILE_CRUMBLE_01,
+};
+
@@ -0,0 +1,16 @@
+// tile_crumble_02
+use crate::wasm4::*;
+use crate::utils::RawImage;
+
+const TILE_CRUMBLE_02_WIDTH: u32 = 16;
+const TILE_CRUMBLE_02_HEIGHT: u32 = 16;
+const TILE_CRUMBLE_02_FLAGS: u32 = BLIT_1BPP;
+const TILE_CRUMBLE_02: [u8; 32] = [ 0xff,0xff,0x82,0x01,0x92,0x11,0x81,0x01,0x80,0xa1,0x80,0x11,0x84,0x11,0xff,0x7f,0x80,0x81,0xa0,0x41,0x80,0x81,0x83,0x05,0x82,0x01,0x92,0x11,0x82,0x01,0xff,0xff ];
+
+pub const RAWIMG_TILE_CRUMBLE_02: RawImage = RawImage {
+    width: TILE_CRUMBLE_02_WIDTH,
+    height: TILE_CRUMBLE_02_HEIGHT,
+    flags: TILE_CRUMBLE_02_FLAGS,
+    data: &TILE_CRUMBLE_02,
+};
+
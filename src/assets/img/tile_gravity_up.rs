@@ -0,0 +1,16 @@
+// tile_gravity_up
+use crate::wasm4::*;
+use crate::utils::RawImage;
+
+const TILE_GRAVITY_UP_WIDTH: u32 = 16;
+const TILE_GRAVITY_UP_HEIGHT: u32 = 16;
+const TILE_GRAVITY_UP_FLAGS: u32 = BLIT_1BPP;
+const TILE_GRAVITY_UP: [u8; 32] = [ 0x00,0x00,0x01,0x80,0x03,0xc0,0x06,0x60,0x0c,0x30,0x00,0x00,0x01,0x80,0x03,0xc0,0x06,0x60,0x0c,0x30,0x00,0x00,0x01,0x80,0x03,0xc0,0x06,0x60,0x0c,0x30,0x00,0x00 ];
+
+pub const RAWIMG_TILE_GRAVITY_UP: RawImage = RawImage {
+    width: TILE_GRAVITY_UP_WIDTH,
+    height: TILE_GRAVITY_UP_HEIGHT,
+    flags: TILE_GRAVITY_UP_FLAGS,
+    data: &TILE_GRAVITY_UP,
+};
+
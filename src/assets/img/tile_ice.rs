@@ -0,0 +1,16 @@
+// tile_ice
+use crate::wasm4::*;
+use crate::utils::RawImage;
+
+const TILE_ICE_WIDTH: u32 = 16;
+const TILE_ICE_HEIGHT: u32 = 16;
+const TILE_ICE_FLAGS: u32 = BLIT_1BPP;
+const TILE_ICE: [u8; 32] = [ 0xff,0xff,0x80,0x01,0xb0,0x01,0x98,0x01,0x8c,0x01,0x80,0x01,0x80,0x31,0x80,0x19,0x80,0x01,0x80,0x01,0x8c,0x01,0x86,0x01,0x80,0x01,0x80,0x21,0x80,0x01,0xff,0xff ];
+
+pub const RAWIMG_TILE_ICE: RawImage = RawImage {
+    width: TILE_ICE_WIDTH,
+    height: TILE_ICE_HEIGHT,
+    flags: TILE_ICE_FLAGS,
+    data: &TILE_ICE,
+};
+
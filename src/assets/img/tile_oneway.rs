@@ -0,0 +1,16 @@
+// tile_oneway
+use crate::wasm4::*;
+use crate::utils::RawImage;
+
+const TILE_ONEWAY_WIDTH: u32 = 16;
+const TILE_ONEWAY_HEIGHT: u32 = 16;
+const TILE_ONEWAY_FLAGS: u32 = BLIT_2BPP;
+const TILE_ONEWAY: [u8; 64] = [ 0x55,0x55,0x55,0x55,0x7f,0xff,0xff,0xfd,0x6e,0xee,0xee,0xed,0x55,0x55,0x55,0x55,0x10,0x00,0x00,0x04,0x10,0x00,0x00,0x04,0x00,0x00,0x00,0x04,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00 ];
+
+pub const RAWIMG_TILE_ONEWAY: RawImage = RawImage {
+    width: TILE_ONEWAY_WIDTH,
+    height: TILE_ONEWAY_HEIGHT,
+    flags: TILE_ONEWAY_FLAGS,
+    data: &TILE_ONEWAY,
+};
+
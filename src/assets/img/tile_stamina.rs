@@ -0,0 +1,16 @@
+// tile_stamina
+use crate::wasm4::*;
+use crate::utils::RawImage;
+
+const TILE_STAMINA_WIDTH: u32 = 16;
+const TILE_STAMINA_HEIGHT: u32 = 16;
+const TILE_STAMINA_FLAGS: u32 = BLIT_2BPP;
+const TILE_STAMINA: [u8; 64] = [ 0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x05,0x50,0x00,0x00,0x5a,0xa5,0x00,0x01,0xaf,0xfa,0x40,0x11,0xbf,0xaa,0x44,0x65,0xbe,0xaa,0x58,0x69,0xba,0xaa,0x69,0x6a,0x6a,0xa9,0xa9,0x1a,0x9a,0xa6,0xa4,0x05,0x5a,0xa5,0x54,0x00,0x1a,0xa4,0x00,0x00,0x05,0x50,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00,0x00 ];
+
+pub const RAWIMG_TILE_STAMINA: RawImage = RawImage {
+    width: TILE_STAMINA_WIDTH,
+    height: TILE_STAMINA_HEIGHT,
+    flags: TILE_STAMINA_FLAGS,
+    data: &TILE_STAMINA,
+};
+
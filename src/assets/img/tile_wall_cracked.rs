@@ -0,0 +1,16 @@
+// tile_wall_cracked
+use crate::wasm4::*;
+use crate::utils::RawImage;
+
+const TILE_WALL_CRACKED_WIDTH: u32 = 16;
+const TILE_WALL_CRACKED_HEIGHT: u32 = 16;
+const TILE_WALL_CRACKED_FLAGS: u32 = BLIT_1BPP;
+const TILE_WALL_CRACKED: [u8; 32] = [ 0xff,0xff,0xc0,0x05,0xa0,0x0b,0x82,0x11,0x82,0x21,0x81,0x41,0x80,0x81,0x81,0x01,0x83,0x01,0x84,0x81,0x88,0x61,0x90,0x11,0xa0,0x09,0xa0,0x05,0xc0,0x03,0xff,0xff ];
+
+pub const RAWIMG_TILE_WALL_CRACKED: RawImage = RawImage {
+    width: TILE_WALL_CRACKED_WIDTH,
+    height: TILE_WALL_CRACKED_HEIGHT,
+    flags: TILE_WALL_CRACKED_FLAGS,
+    data: &TILE_WALL_CRACKED,
+};
+
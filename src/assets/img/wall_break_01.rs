@@ -0,0 +1,16 @@
+// wall_break_01
+use crate::wasm4::*;
+use crate::utils::RawImage;
+
+const WALL_BREAK_01_WIDTH: u32 = 16;
+const WALL_BREAK_01_HEIGHT: u32 = 16;
+const WALL_BREAK_01_FLAGS: u32 = BLIT_1BPP;
+const WALL_BREAK_01: [u8; 32] = [ 0x00,0x00,0x7c,0xfc,0x44,0x84,0x44,0x44,0x7c,0x48,0x00,0x00,0x1e,0x3e,0x12,0x22,0x12,0x22,0x1e,0x14,0x00,0x1c,0x78,0x00,0x48,0x7c,0x48,0x44,0x78,0x7c,0x00,0x00 ];
+
+pub const RAWIMG_WALL_BREAK_01: RawImage = RawImage {
+    width: WALL_BREAK_01_WIDTH,
+    height: WALL_BREAK_01_HEIGHT,
+    flags: WALL_BREAK_01_FLAGS,
+    data: &WALL_BREAK_01,
+};
+
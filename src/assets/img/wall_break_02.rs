@@ -0,0 +1,16 @@
+// wall_break_02
+use crate::wasm4::*;
+use crate::utils::RawImage;
+
+const WALL_BREAK_02_WIDTH: u32 = 16;
+const WALL_BREAK_02_HEIGHT: u32 = 16;
+const WALL_BREAK_02_FLAGS: u32 = BLIT_1BPP;
+const WALL_BREAK_02: [u8; 32] = [ 0x00,0x00,0x00,0x00,0x30,0x18,0x30,0x18,0x00,0x00,0x03,0x00,0x03,0x0c,0x00,0x0c,0x60,0x00,0x60,0x60,0x00,0x60,0x0c,0x00,0x0c,0x0c,0x00,0x0c,0x00,0x00,0x00,0x00 ];
+
+pub const RAWIMG_WALL_BREAK_02: RawImage = RawImage {
+    width: WALL_BREAK_02_WIDTH,
+    height: WALL_BREAK_02_HEIGHT,
+    flags: WALL_BREAK_02_FLAGS,
+    data: &WALL_BREAK_02,
+};
+
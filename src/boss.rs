@@ -0,0 +1,240 @@
+use crate::wasm4::*;
+use crate::utils::*;
+use crate::assets::img::*;
+use crate::dragon::*;
+use crate::entity::*;
+
+// -------------------------------
+// ボス(ラストバトル)
+// -------------------------------
+// 座標は闘技場のワールド座標(闘技場は画面と同じ160x160)
+pub const BOSS_WIDTH: i16 = 32;
+pub const BOSS_HEIGHT: i16 = 32;
+const BOSS_MAX_HP: u8 = 6;
+const BOSS_PHASE2_HP: u8 = 3;    // これ以下で第2形態
+const BOSS_FLOAT_Y: i16 = 24;
+const BOSS_GROUND_Y: i16 = 112;  // 着地したときのy(床の上端 - 高さ)
+const BOSS_MIN_X: i16 = 16;
+const BOSS_MAX_X: i16 = 112;
+const BOSS_STOMP_DEPTH: i16 = 10; // 頭の上端からこの深さまでを踏める範囲とする
+const BOSS_MAX_FALL_SPD: i16 = 6;
+const BOSS_DEFEAT_FRAME: u16 = 120;
+const BOSS_SHOT_SIZE: i16 = 8;
+
+#[derive(Copy, Clone, PartialEq)]
+enum BossState {
+  Appear,   // 画面の上から降りてくる
+  Float,    // 竜の上空を追いかける
+  Attack,   // 弾を撃つ
+  Charge,   // 落下の予備動作(震える)
+  Slam,     // 落下
+  Stun,     // 着地して目を回している(踏める)
+  Hurt,     // 踏まれた
+  Rise,     // 浮上
+  Defeated, // 撃破演出
+  Gone,
+}
+
+// 形態ごとの行動パラメータ
+struct BossPhase {
+  float_frames: u16, // 追いかける時間
+  chase_spd: i16,
+  shot_wait: u16,    // 弾を撃つ間隔
+  shot_count: u8,    // 1回の攻撃で撃つ回数
+  shot_spread: bool, // 3方向に撃つ
+  stun_frames: u16,  // 踏める時間
+}
+const BOSS_PHASE_1: BossPhase = BossPhase { float_frames: 120, chase_spd: 1, shot_wait: 30, shot_count: 3, shot_spread: false, stun_frames: 120 };
+const BOSS_PHASE_2: BossPhase = BossPhase { float_frames: 80,  chase_spd: 2, shot_wait: 24, shot_count: 4, shot_spread: true,  stun_frames: 90 };
+
+pub struct Boss {
+  anim: [Timeline; 3], // 通常, 第2形態, 気絶
+  pos: Vec2i,
+  vel_y: i16,
+  hp: u8,
+  state: BossState,
+  state_frames: u16,
+  shot_num: u8,
+}
+
+impl Boss {
+  pub fn new() -> Self {
+    Self {
+      anim: [
+        Timeline::new(ANIM_BOSS_FLOAT, [12, 12].to_vec()),
+        Timeline::new(ANIM_BOSS_ANGRY, [6, 6].to_vec()),
+        Timeline::new(ANIM_BOSS_STUN,  [10, 10].to_vec()),
+      ],
+      pos: Vec2i::new(64, -BOSS_HEIGHT),
+      vel_y: 0,
+      hp: BOSS_MAX_HP,
+      state: BossState::Appear,
+      state_frames: 0,
+      shot_num: 0,
+    }
+  }
+  pub fn reset(&mut self) {
+    *self = Self::new();
+  }
+  pub fn is_defeated(&self) -> bool { self.state == BossState::Gone }
+  // (x, y, w, h)
+  fn get_rect(&self) -> (i16, i16, i16, i16) { (self.pos.x, self.pos.y, BOSS_WIDTH, BOSS_HEIGHT) }
+
+  fn get_phase(&self) -> &'static BossPhase {
+    if self.hp <= BOSS_PHASE2_HP { &BOSS_PHASE_2 } else { &BOSS_PHASE_1 }
+  }
+  fn change_state(&mut self, state: BossState) {
+    self.state = state;
+    self.state_frames = 0;
+  }
+
+  // 弾はステージのエンティティとして出現させる
+  pub fn update(&mut self, dragon: &mut Dragon, entities: &mut EntityPool) {
+    self.state_frames = self.state_frames.saturating_add(1);
+    for anim in self.anim.iter_mut() { anim.play(); }
+    let phase = self.get_phase();
+
+    match self.state {
+      BossState::Appear => {
+        self.pos.y += 1;
+        if self.pos.y >= BOSS_FLOAT_Y {
+          self.pos.y = BOSS_FLOAT_Y;
+          self.change_state(BossState::Float);
+        }
+      }
+      BossState::Float => {
+        // 竜の真上へ向かう
+        let target_x = (dragon.pos.x + DRAGON_WIDTH / 2 - BOSS_WIDTH / 2).clamp(BOSS_MIN_X, BOSS_MAX_X);
+        if self.pos.x < target_x { self.pos.x = (self.pos.x + phase.chase_spd).min(target_x); }
+        if self.pos.x > target_x { self.pos.x = (self.pos.x - phase.chase_spd).max(target_x); }
+        // ふわふわ
+        self.pos.y = BOSS_FLOAT_Y + [0, 1, 2, 1][(self.state_frames / 8 % 4) as usize];
+        if self.state_frames >= phase.float_frames {
+          self.shot_num = 0;
+          self.change_state(BossState::Attack);
+        }
+      }
+      BossState::Attack => {
+        if self.state_frames.is_multiple_of(phase.shot_wait) {
+          self.fire(phase.shot_spread, entities);
+          self.shot_num += 1;
+          if self.shot_num >= phase.shot_count { self.change_state(BossState::Charge); }
+        }
+      }
+      BossState::Charge => {
+        if self.state_frames >= 30 {
+          self.vel_y = 0;
+          self.change_state(BossState::Slam);
+        }
+      }
+      BossState::Slam => {
+        self.vel_y = (self.vel_y + 1).min(BOSS_MAX_FALL_SPD);
+        self.pos.y += self.vel_y;
+        if self.pos.y >= BOSS_GROUND_Y {
+          self.pos.y = BOSS_GROUND_Y;
+          tone(80 | (40 << 16), 20, 100, TONE_NOISE);
+          self.change_state(BossState::Stun);
+        }
+      }
+      BossState::Stun => {
+        if self.state_frames >= phase.stun_frames { self.change_state(BossState::Rise); }
+      }
+      BossState::Hurt => {
+        if self.state_frames >= 40 {
+          if self.hp == 0 { self.change_state(BossState::Defeated); }
+          else { self.change_state(BossState::Rise); }
+        }
+      }
+      BossState::Rise => {
+        self.pos.y -= 2;
+        if self.pos.y <= BOSS_FLOAT_Y {
+          self.pos.y = BOSS_FLOAT_Y;
+          self.change_state(BossState::Float);
+        }
+      }
+      BossState::Defeated => {
+        // 断末魔
+        if self.state_frames.is_multiple_of(20) { tone(200 | (50 << 16), 16, 60, TONE_NOISE); }
+        if self.state_frames >= BOSS_DEFEAT_FRAME { self.change_state(BossState::Gone); }
+      }
+      BossState::Gone => {}
+    }
+
+    self.check_dragon(dragon);
+    self.check_breath(entities);
+  }
+
+  // 体の下から弾を撃つ
+  fn fire(&mut self, spread: bool, entities: &mut EntityPool) {
+    let pos = Vec2i::new(self.pos.x + (BOSS_WIDTH - BOSS_SHOT_SIZE) / 2, self.pos.y + BOSS_HEIGHT - BOSS_SHOT_SIZE);
+    let dirs: &[i16] = if spread { &[-1, 0, 1] } else { &[0] };
+    for dx in dirs {
+      entities.spawn(EntityKind::BossShot, pos, Vec2i::new(*dx, 2));
+    }
+    tone(600 | (300 << 16), 8, 40, TONE_PULSE2);
+  }
+
+  // 気絶中だけダメージが通る
+  fn damage(&mut self) {
+    self.hp -= 1;
+    tone(880 | (220 << 16), 12, 80, TONE_PULSE1);
+    self.change_state(BossState::Hurt);
+  }
+  // 気絶中はブレスも当たる
+  fn check_breath(&mut self, entities: &mut EntityPool) {
+    if self.state != BossState::Stun { return }
+    let (x, y, w, h) = self.get_rect();
+    let Some(breath) = entities.query_mut(x, y, w, h).find(|e| e.kind == EntityKind::Breath) else { return };
+    breath.kill();
+    entities.push_hit(HitEvent { shooter: EntityKind::Breath, target: HitTarget::Boss });
+    self.damage();
+  }
+
+  fn check_dragon(&mut self, dragon: &mut Dragon) {
+    if dragon.is_death { return }
+    if !is_overlap(self.get_rect(), (dragon.pos.x, dragon.pos.y, DRAGON_WIDTH, DRAGON_HEIGHT)) { return }
+    match self.state {
+      // 気絶中は頭を踏める
+      BossState::Stun => {
+        let dragon_btm = dragon.pos.y + DRAGON_HEIGHT;
+        if dragon.is_falling() && dragon_btm <= self.pos.y + BOSS_STOMP_DEPTH {
+          dragon.bounce();
+          self.damage();
+        }
+      }
+      BossState::Hurt | BossState::Defeated | BossState::Gone => {}
+      // それ以外は体当たりでダメージ(竜の食らい判定で見る)
+      _ => {
+        if !is_overlap(self.get_rect(), dragon.get_hurtbox()) { return }
+        dragon.take_damage(dragon.get_knockback_dir(self.pos.x + BOSS_WIDTH / 2));
+      }
+    }
+  }
+
+  pub fn draw(&self, offset_x: i16, offset_y: i16) {
+    if self.state == BossState::Gone { return }
+    // 被弾・撃破時は点滅
+    let is_flash = matches!(self.state, BossState::Hurt | BossState::Defeated);
+    if is_flash && self.state_frames & 0b10 == 0b10 { return }
+
+    let mut x = self.pos.x + offset_x;
+    let y = self.pos.y + offset_y;
+    // 落下前と撃破時は震える
+    if matches!(self.state, BossState::Charge | BossState::Defeated) { x += [-1, 1][(self.state_frames / 2 % 2) as usize]; }
+    let anim = match self.state {
+      BossState::Stun | BossState::Hurt | BossState::Defeated => &self.anim[2],
+      _ if self.hp <= BOSS_PHASE2_HP => &self.anim[1],
+      _ => &self.anim[0],
+    };
+    anim.draw(x as i32, y as i32);
+  }
+
+  // 画面上部のHPゲージ
+  pub fn draw_hp_bar(&self) {
+    if self.state == BossState::Gone { return }
+    set_drawcolor(1, 4);
+    rect(40, 4, 80, 6);
+    set_drawcolor(3, 3);
+    rect(41, 5, 78 * self.hp as u32 / BOSS_MAX_HP as u32, 4);
+  }
+}
@@ -0,0 +1,215 @@
+use crate::wasm4::*;
+use crate::utils::*;
+use crate::dialog::*;
+use crate::assets::img::*;
+use crate::lang::*;
+
+// -------------------------------
+// カットシーン
+// -------------------------------
+const MAX_CUTSCENE_SPRITE: usize = 4;
+const CUTSCENE_SKIP_BTN: u8 = BTN_X;
+const CUTSCENE_SKIP_FRAME: u16 = 60; // スキップに必要な長押し時間
+const CUTSCENE_TEXT_Y: i32 = 110;
+
+// カットシーンの命令
+// スプライトの座標はワールド座標、カメラの座標は描画時のオフセット
+#[derive(Copy, Clone)]
+pub enum CutsceneCmd {
+  Text(StrId),                                         // テキスト表示(改行は'\n')
+  ClearText,                                           // テキスト消去
+  Dialog(StrId, Option<&'static Image>),               // ダイアログを開き、閉じられるまで待機(本文, 顔グラフィック)
+  Wait(u16),                                           // 指定フレーム待機
+  WaitButton,                                          // Zが押されるまで待機
+  WaitOrButton(u16),                                   // 指定フレーム待機(Zで打ち切り)
+  Sprite(u8, &'static [&'static Image], u8, Vec2i),    // スプライトを表示(番号, 画像, 1コマの長さ, 座標)
+  Anim(u8, &'static [&'static Image], u8),             // スプライトのアニメーションを変更
+  Move(u8, Vec2i, u16),                                // スプライトを指定フレームかけて移動
+  Flip(u8, bool),                                      // スプライトの左右反転
+  Hide(u8),                                            // スプライトを非表示
+  Palette(&'static [u32; 4]),                          // パレットを変更
+  Sound(u32, u32, u32, u32),                           // 音を鳴らす(tone()の引数そのまま)
+  Pan(Vec2i, u16),                                     // カメラを指定フレームかけて移動(1以下で即時)
+}
+
+#[derive(Copy, Clone)]
+struct CutsceneSprite {
+  images: &'static [&'static Image],
+  wait: u8,
+  frame_count: u8,
+  now_idx: u8,
+  pos: Vec2i,
+  xflip: bool,
+  visible: bool,
+}
+impl CutsceneSprite {
+  const fn new() -> Self {
+    Self { images: &[], wait: 0, frame_count: 0, now_idx: 0, pos: Vec2i::new(0, 0), xflip: false, visible: false }
+  }
+  fn set_anim(&mut self, images: &'static [&'static Image], wait: u8) {
+    self.images = images;
+    self.wait = wait;
+    self.frame_count = 0;
+    self.now_idx = 0;
+  }
+  fn play(&mut self) {
+    if !self.visible || self.images.len() <= 1 { return }
+    self.frame_count += 1;
+    if self.frame_count >= self.wait {
+      self.frame_count = 0;
+      self.now_idx += 1;
+      if self.now_idx as usize >= self.images.len() { self.now_idx = 0; }
+    }
+  }
+  fn draw(&self, offset_x: i16, offset_y: i16) {
+    if !self.visible || self.images.is_empty() { return }
+    let flag = if self.xflip { BLIT_FLIP_X } else { 0 };
+    self.images[self.now_idx as usize].drawf((self.pos.x + offset_x) as i32, (self.pos.y + offset_y) as i32, flag);
+  }
+}
+
+// カットシーンの再生機
+// 1フレームに1命令ずつ実行し、待機や移動の命令は完了するまで次に進まない
+pub struct CutscenePlayer {
+  script: &'static [CutsceneCmd],
+  now_idx: usize,
+  cmd_frames: u16,
+  move_from: Vec2i,
+  sprites: [CutsceneSprite; MAX_CUTSCENE_SPRITE],
+  text: &'static str,
+  dialog: Dialog,
+  cam: Vec2i,
+}
+impl CutscenePlayer {
+  pub fn new() -> Self {
+    Self {
+      script: &[],
+      now_idx: 0,
+      cmd_frames: 0,
+      move_from: Vec2i::zero(),
+      sprites: [CutsceneSprite::new(); MAX_CUTSCENE_SPRITE],
+      text: "",
+      dialog: Dialog::new(4, 116, 152, 40, DIALOG_DEFAULT_SPEED),
+      cam: Vec2i::zero(),
+    }
+  }
+  // 再生開始(camは再生前のカメラ座標)
+  pub fn play(&mut self, script: &'static [CutsceneCmd], cam: Vec2i) {
+    self.script = script;
+    self.now_idx = 0;
+    self.cmd_frames = 0;
+    self.sprites = [CutsceneSprite::new(); MAX_CUTSCENE_SPRITE];
+    self.text = "";
+    self.dialog.close();
+    self.cam = cam;
+  }
+  pub fn is_playing(&self) -> bool { self.now_idx < self.script.len() }
+  pub fn get_cam(&self) -> Vec2i { self.cam }
+
+  pub fn update(&mut self) {
+    if !self.is_playing() { return }
+    // 長押しでスキップ
+    if get_pressed_time(CUTSCENE_SKIP_BTN) >= CUTSCENE_SKIP_FRAME {
+      self.skip();
+      return
+    }
+
+    for sprite in self.sprites.iter_mut() { sprite.play(); }
+
+    let is_done = self.exec(self.script[self.now_idx]);
+    self.cmd_frames = self.cmd_frames.saturating_add(1);
+    if is_done {
+      self.now_idx += 1;
+      self.cmd_frames = 0;
+    }
+  }
+
+  // 命令を1フレーム分実行し、完了したかを返す
+  fn exec(&mut self, cmd: CutsceneCmd) -> bool {
+    match cmd {
+      CutsceneCmd::Text(id) => { self.text = tr(id); true }
+      CutsceneCmd::ClearText => { self.text = ""; true }
+      CutsceneCmd::Dialog(id, portrait) => {
+        if self.cmd_frames == 0 { self.dialog.open(tr(id), portrait); }
+        else { self.dialog.update(); }
+        !self.dialog.is_open()
+      }
+      CutsceneCmd::Wait(frames) => { self.cmd_frames + 1 >= frames }
+      CutsceneCmd::WaitButton => { self.cmd_frames > 0 && is_just_pressed(BTN_Z) }
+      CutsceneCmd::WaitOrButton(frames) => {
+        self.cmd_frames + 1 >= frames || (self.cmd_frames > 0 && is_just_pressed(BTN_Z))
+      }
+      CutsceneCmd::Sprite(slot, images, wait, pos) => {
+        let sprite = &mut self.sprites[slot as usize];
+        sprite.set_anim(images, wait);
+        sprite.pos = pos;
+        sprite.xflip = false;
+        sprite.visible = true;
+        true
+      }
+      CutsceneCmd::Anim(slot, images, wait) => { self.sprites[slot as usize].set_anim(images, wait); true }
+      CutsceneCmd::Move(slot, to, frames) => {
+        if self.cmd_frames == 0 { self.move_from = self.sprites[slot as usize].pos; }
+        let (pos, is_done) = lerp_pos(self.move_from, to, self.cmd_frames + 1, frames);
+        self.sprites[slot as usize].pos = pos;
+        is_done
+      }
+      CutsceneCmd::Flip(slot, xflip) => { self.sprites[slot as usize].xflip = xflip; true }
+      CutsceneCmd::Hide(slot) => { self.sprites[slot as usize].visible = false; true }
+      CutsceneCmd::Palette(palette) => { set_palette(palette); true }
+      CutsceneCmd::Sound(freq, dur, vol, flags) => { tone(freq, dur, vol, flags); true }
+      CutsceneCmd::Pan(cam, frames) => {
+        if self.cmd_frames == 0 { self.move_from = self.cam; }
+        let (cam, is_done) = lerp_pos(self.move_from, cam, self.cmd_frames + 1, frames);
+        self.cam = cam;
+        is_done
+      }
+    }
+  }
+
+  // 残りの命令を即座に反映して終了する(待機・テキスト・音は飛ばす)
+  fn skip(&mut self) {
+    while self.is_playing() {
+      match self.script[self.now_idx] {
+        CutsceneCmd::Move(slot, to, _) => { self.sprites[slot as usize].pos = to; }
+        CutsceneCmd::Pan(cam, _) => { self.cam = cam; }
+        CutsceneCmd::Text(_) | CutsceneCmd::ClearText | CutsceneCmd::Dialog(..) | CutsceneCmd::Sound(..) |
+        CutsceneCmd::Wait(_) | CutsceneCmd::WaitButton | CutsceneCmd::WaitOrButton(_) => {}
+        cmd => { self.exec(cmd); }
+      }
+      self.now_idx += 1;
+    }
+    self.cmd_frames = 0;
+    self.text = "";
+    self.dialog.close();
+  }
+
+  pub fn draw(&self) {
+    if !self.is_playing() { return }
+    for sprite in self.sprites.iter() { sprite.draw(self.cam.x, self.cam.y); }
+
+    // テキスト
+    if !self.text.is_empty() {
+      set_drawcolor(1, 4);
+      rect(4, CUTSCENE_TEXT_Y - 6, 152, 32);
+      FONT_MAIN.draw_center_x(self.text, CUTSCENE_TEXT_Y);
+    }
+    self.dialog.draw();
+    // スキップのゲージ
+    let skip_frames = get_pressed_time(CUTSCENE_SKIP_BTN);
+    if skip_frames > 0 {
+      set_drawcolor(3, 3);
+      rect(120, 2, (skip_frames.min(CUTSCENE_SKIP_FRAME) * 36 / CUTSCENE_SKIP_FRAME) as u32, 4);
+    }
+  }
+}
+
+// fromからtoへの線形補間(frame/frames)
+fn lerp_pos(from: Vec2i, to: Vec2i, frame: u16, frames: u16) -> (Vec2i, bool) {
+  if frame >= frames { return (to, true) }
+  let t = frame as i32;
+  let n = frames as i32;
+  let x = from.x as i32 + (to.x as i32 - from.x as i32) * t / n;
+  let y = from.y as i32 + (to.y as i32 - from.y as i32) * t / n;
+  (Vec2i::new(x as i16, y as i16), false)
+}
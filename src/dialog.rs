@@ -0,0 +1,202 @@
+use crate::wasm4::*;
+use crate::utils::*;
+use crate::font::*;
+use crate::assets::img::*;
+
+// -------------------------------
+// ダイアログ(テキストボックス)
+// -------------------------------
+const MAX_DIALOG_LINES: usize = 24;
+const DIALOG_PADDING: i32 = 4;
+const DIALOG_PORTRAIT_W: i32 = 20; // 顔グラフィック(16px)と余白
+const DIALOG_BLINK_FRAME: u8 = 16; // 送りマークの点滅間隔
+pub const DIALOG_DEFAULT_SPEED: u8 = 2;
+
+pub struct Dialog {
+  x: i32,
+  y: i32,
+  w: u32,
+  h: u32,
+  msg: &'static str,
+  portrait: Option<&'static Image>,
+  speed: u8, // 1文字を表示するのにかかるフレーム数(0なら一括表示)
+  lines: [(u16, u16); MAX_DIALOG_LINES], // 各行の(開始, 終了)位置
+  line_count: usize,
+  page_idx: usize,
+  revealed: u16, // 現在のページで表示済みの文字数
+  frame_count: u8,
+  blink_frames: u8,
+  is_open: bool,
+}
+impl Dialog {
+  pub fn new(x: i32, y: i32, w: u32, h: u32, speed: u8) -> Self {
+    Self {
+      x, y, w, h,
+      msg: "",
+      portrait: None,
+      speed,
+      lines: [(0, 0); MAX_DIALOG_LINES],
+      line_count: 0,
+      page_idx: 0,
+      revealed: 0,
+      frame_count: 0,
+      blink_frames: 0,
+      is_open: false,
+    }
+  }
+  pub fn is_open(&self) -> bool { self.is_open }
+
+  pub fn open(&mut self, msg: &'static str, portrait: Option<&'static Image>) {
+    self.msg = msg;
+    self.portrait = portrait;
+    self.page_idx = 0;
+    self.revealed = 0;
+    self.frame_count = 0;
+    self.blink_frames = 0;
+    self.is_open = true;
+    self.layout();
+    if self.speed == 0 { self.revealed = self.get_page_chars(); }
+  }
+  pub fn close(&mut self) {
+    self.is_open = false;
+  }
+
+  pub fn update(&mut self) {
+    if !self.is_open { return }
+    self.blink_frames = self.blink_frames.wrapping_add(1);
+    let page_chars = self.get_page_chars();
+
+    // 全文表示済み: Zで次のページへ、最後のページなら閉じる
+    if self.revealed >= page_chars {
+      if is_just_pressed(BTN_Z) {
+        if self.page_idx + 1 < self.get_page_count() {
+          self.page_idx += 1;
+          self.revealed = if self.speed == 0 { self.get_page_chars() } else { 0 };
+          self.frame_count = 0;
+        }
+        else { self.close(); }
+      }
+      return
+    }
+    // 文字送り中: Zでページを一括表示
+    if is_just_pressed(BTN_Z) {
+      self.revealed = page_chars;
+      return
+    }
+    self.frame_count += 1;
+    if self.frame_count >= self.speed {
+      self.frame_count = 0;
+      self.revealed += 1;
+    }
+  }
+
+  pub fn draw(&self) {
+    if !self.is_open { return }
+    // 枠
+    set_drawcolor(1, 4);
+    rect(self.x, self.y, self.w, self.h);
+
+    // 顔グラフィック
+    let mut text_x = self.x + DIALOG_PADDING;
+    if let Some(img) = self.portrait {
+      img.draw(text_x, self.y + DIALOG_PADDING);
+      text_x += DIALOG_PORTRAIT_W;
+    }
+
+    // 本文
+    let first = self.page_idx * self.get_lines_per_page();
+    let last = (first + self.get_lines_per_page()).min(self.line_count);
+    let mut rest = self.revealed as usize;
+    for (i, line) in self.lines[first..last].iter().enumerate() {
+      if rest == 0 { break }
+      // 表示済みの文字数ぶんだけ切り出す
+      let line_str = &self.msg[line.0 as usize..line.1 as usize];
+      let end = line_str.char_indices().nth(rest).map_or(line_str.len(), |(idx, _)| idx);
+      rest -= line_str[..end].chars().count().min(rest);
+      FONT_MAIN.draw(&line_str[..end], text_x, self.y + DIALOG_PADDING + i as i32 * FONT_LINE_H);
+    }
+
+    // 送りマーク(▼)
+    if self.revealed >= self.get_page_chars() && self.blink_frames % (DIALOG_BLINK_FRAME * 2) < DIALOG_BLINK_FRAME {
+      let ax = self.x + self.w as i32 - 10;
+      let ay = self.y + self.h as i32 - 7;
+      set_drawcolor_idx(0x04); // フォント描画で色が変わっているので戻す
+      hline(ax, ay, 5);
+      hline(ax + 1, ay + 1, 3);
+      hline(ax + 2, ay + 2, 1);
+    }
+  }
+
+  // 1行に入る幅(px)
+  fn get_text_w(&self) -> i32 {
+    let mut inner_w = self.w as i32 - DIALOG_PADDING * 2;
+    if self.portrait.is_some() { inner_w -= DIALOG_PORTRAIT_W; }
+    inner_w
+  }
+  fn get_lines_per_page(&self) -> usize {
+    ((self.h as i32 - DIALOG_PADDING * 2 + FONT_LINE_H - FONT_SIZE as i32) / FONT_LINE_H).max(1) as usize
+  }
+  fn get_page_count(&self) -> usize {
+    self.line_count.div_ceil(self.get_lines_per_page()).max(1)
+  }
+  // 現在のページの文字数
+  fn get_page_chars(&self) -> u16 {
+    let first = self.page_idx * self.get_lines_per_page();
+    let last = (first + self.get_lines_per_page()).min(self.line_count);
+    self.lines[first..last].iter().map(|l| self.msg[l.0 as usize..l.1 as usize].chars().count() as u16).sum()
+  }
+
+  // 英語は単語単位、日本語は文字単位で折り返して行に分割する
+  fn layout(&mut self) {
+    let msg = self.msg;
+    let max_w = self.get_text_w();
+    self.line_count = 0;
+    let mut line_start = 0;
+    let mut line_w = 0;
+    let mut brk: Option<(usize, usize)> = None; // 折り返せる位置(行の終わり, 次の行の始まり)
+    for (i, c) in msg.char_indices() {
+      // 明示的な改行
+      if c == '\n' {
+        self.push_line(line_start, i);
+        line_start = i + 1;
+        line_w = 0;
+        brk = None;
+        continue
+      }
+      if i > line_start && can_break_before(c) { brk = Some((i, i)); }
+      let w = FONT_MAIN.get_char_width(c);
+      // 行からあふれる
+      if i > line_start && line_w + w > max_w {
+        if c == ' ' {
+          // 空白の位置でちょうど折り返す
+          self.push_line(line_start, i);
+          line_start = i + 1;
+          line_w = 0;
+          brk = None;
+          continue
+        }
+        if let Some((end, next)) = brk {
+          // 直前の折り返せる位置で折り返す
+          self.push_line(line_start, end);
+          line_start = next;
+          line_w = FONT_MAIN.get_text_width(&msg[next..i]);
+        }
+        else {
+          // 単語が1行より長いので強制的に折り返す
+          self.push_line(line_start, i);
+          line_start = i;
+          line_w = 0;
+        }
+        brk = None;
+      }
+      if c == ' ' { brk = Some((i, i + 1)); }
+      line_w += w;
+    }
+    if line_start < msg.len() { self.push_line(line_start, msg.len()); }
+  }
+  fn push_line(&mut self, start: usize, end: usize) {
+    if self.line_count >= MAX_DIALOG_LINES { trace("dialog lines exceeded"); return }
+    self.lines[self.line_count] = (start as u16, end as u16);
+    self.line_count += 1;
+  }
+}
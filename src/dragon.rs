@@ -2,16 +2,34 @@ use crate::wasm4::*;
 use crate::utils::*;
 use crate::assets::img::*;
 use crate::stage::*;
+use crate::platform::*;
+use crate::physics::*;
+use crate::upgrade::*;
+use crate::entity::Hitbox;
 
 pub const DRAGON_WIDTH: i16 = 16;
 pub const DRAGON_HEIGHT: i16 = 16;
-const JUMP_POWER: i16 = -4;
+// ダメージを受ける範囲(壁との当たり判定より小さく、足元だけは揃える)
+const HURTBOX: Hitbox = Hitbox::new(3, 3, 10, 13);
 const MAX_HP: u8 = 6;
-const MAX_FALL_SPD_Y: i16 = 4;
-const FUNBARI_TIME: u8 = 60;
-const INV_TIME: u8 = 90;
-const SPRING_FORCE_X: i16 = 12;
-const SPRING_FORCE_Y: i16 = 18;
+const SPRING_BREAK_FORCE: i16 = 6; // ばねの勢いがこれ以上なら、ひび割れた壁を壊せる
+const BREATH_COOLDOWN: u8 = 30;
+const BREATH_MOTION_TIME: u8 = 12; // 口を開けている時間
+const BREATH_SPD: i16 = 3;
+const ICE_SLIDE_TIME: u8 = 12;   // 氷の上で減速するまでのフレーム数
+const ONEWAY_DROP_TIME: u8 = 8; // すり抜け床から降りた後、床を無視する時間
+const WALL_SLIDE_SPD: i16 = 1;  // 壁ずり中の落下速度
+const WALL_JUMP_FORCE: i16 = 6; // 壁ジャンプで壁から離れる勢い
+const WALL_JUMP_TIME: u8 = 10;  // 壁ジャンプのモーションを見せる時間
+const DASH_TIME: u8 = 8;
+const DASH_SPD: i16 = 4;        // タイルより小さくして壁をすり抜けないようにする
+const DASH_DIAG_SPD: i16 = 3;   // 斜め
+const AFTERIMAGE_NUM: usize = 4;
+const AFTERIMAGE_LIFE: u8 = 10;
+const STAMINA_PER_LEVEL: u8 = 20;   // 踏ん張りの強化1段階で増える飛行の体力
+const STAMINA_REFILL_SPD: u8 = 4;   // 着地中に1fで回復する量
+const HURT_FLASH_COL: u16 = 0x0124; // 被弾時の点滅色(明暗を反転)
+const HEART_HEAL: u8 = 2;
 
 #[derive(Copy, Clone, PartialEq)]
 enum State {
@@ -21,14 +39,26 @@ enum State {
   Fly,
   Fall,
   Death,
+  Breath,
+  WallSlide,
+  WallJump,
+  Hurt,
+}
+// ダッシュの残像
+#[derive(Copy, Clone)]
+struct AfterImage {
+  image: &'static Image,
+  pos: Vec2i,
+  flags: u32,
+  life: u8,
 }
 pub struct Dragon {
-  anim: [Timeline; 6],
+  anim: [Timeline; 10],
   pub pos: Vec2i,
   vel: Vec2i,
   pub force: Vec2i,
   pub hp: u8,
-  pub frag_count: u8,
+  pub upgrades: UpgradeLedger, // 欠片で買った強化
   
   now_state: State,
   old_state: State,
@@ -37,8 +67,29 @@ pub struct Dragon {
   move_frames: u8,
   jump_frames: u8,
   fly_frames: u8,
+  stamina: u8,           // 踏ん張りの残り時間
   inv_frames: u8,
+  hurt_frames: u8,       // 被弾後の操作できない残り時間
   death_frames: u8,
+  breath_frames: u8,
+  breath_cooldown: u8,
+  riding: Option<usize>, // 乗っている移動床
+  drop_frames: u8,
+  on_oneway: bool,       // すり抜け床の上にいる
+  surface: Surface,      // 立っている床の性質
+  slide_frames: u8,
+  wall_dir: i16,         // 張り付いている壁の方向(左:-1, 右:1, なし:0)
+  wall_jump_frames: u8,
+  pub can_wall_jump: bool, // ステージごとの設定
+  dash_frames: u8,
+  dash_dir: Vec2i,
+  can_dash: bool,         // 着地するまでに1回だけ
+  afterimages: [Option<AfterImage>; AFTERIMAGE_NUM],
+  afterimage_idx: usize,
+  coyote_frames: u8,     // 足場から離れてからのフレーム数
+  double_jump_ready: bool, // 着地するまでに1回だけ
+  gravity_frames: u8,
+  pub physics: PhysicsProfile, // ステージごとの設定
   is_jump: bool,
   is_inv: bool,
   pub is_death: bool,
@@ -50,9 +101,14 @@ pub struct Dragon {
 }
 
 fn is_solid_tile(tile: Option<&Tile>) -> bool {
-  if let Some(t) = tile {
-    matches!(t.id, TileId::Wall | TileId::SpringVert | TileId::SpringHori | TileId::NeedleDown | TileId::NeedleLeft | TileId::NeedleRight | TileId::NeedleUp)
-  } else { false }
+  if let Some(t) = tile { t.id.is_solid() } else { false }
+}
+fn is_spring_vert(tile: Option<&Tile>) -> bool {
+  tile.is_some_and(|t| t.id == TileId::SpringVert)
+}
+// 足元のタイルの性質(普通でない方を優先)
+fn get_surface(bl: Option<&Tile>, br: Option<&Tile>) -> Surface {
+  [bl, br].into_iter().flatten().map(|t| t.id.get_surface()).find(|s| *s != Surface::Normal).unwrap_or(Surface::Normal)
 }
 
 impl Dragon {
@@ -60,8 +116,7 @@ impl Dragon {
     self.pos = pos;
     self.vel = Vec2i::zero();
     self.force = Vec2i::zero();
-    self.hp = MAX_HP;
-    self.frag_count = 0;
+    self.hp = self.get_max_hp();
     self.anim[self.now_state as usize].reset();
     self.now_state = State::Idle;
     self.old_state = State::Idle;
@@ -69,8 +124,25 @@ impl Dragon {
     self.move_frames = 0;
     self.jump_frames = 0;
     self.fly_frames = 0;
+    self.stamina = self.get_max_stamina();
     self.inv_frames = 0;
+    self.hurt_frames = 0;
     self.death_frames = 0;
+    self.breath_frames = 0;
+    self.breath_cooldown = 0;
+    self.riding = None;
+    self.drop_frames = 0;
+    self.on_oneway = false;
+    self.surface = Surface::Normal;
+    self.slide_frames = 0;
+    self.wall_dir = 0;
+    self.wall_jump_frames = 0;
+    self.dash_frames = 0;
+    self.can_dash = true;
+    self.afterimages = [None; AFTERIMAGE_NUM];
+    self.coyote_frames = 0;
+    self.double_jump_ready = false;
+    self.gravity_frames = 0;
     self.is_jump = false;
     self.is_inv = false;
     self.is_death = false;
@@ -86,27 +158,76 @@ impl Dragon {
       Timeline::new(ANIM_DRAGON_JUMP, [255].to_vec()),
       Timeline::new(ANIM_DRAGON_FLY,  [4, 4].to_vec()),
       Timeline::new(ANIM_DRAGON_FALL, [255].to_vec()),
-      Timeline::new(ANIM_DRAGON_DEATH, [5, 5, 5, 5].to_vec())
+      Timeline::new(ANIM_DRAGON_DEATH, [5, 5, 5, 5].to_vec()),
+      Timeline::new(ANIM_DRAGON_BREATH, [255].to_vec()),
+      Timeline::new(ANIM_DRAGON_WALL_SLIDE, [255].to_vec()),
+      Timeline::new(ANIM_DRAGON_WALL_JUMP, [255].to_vec()),
+      Timeline::new(ANIM_DRAGON_HURT, [255].to_vec()),
       ],
-      pos: Vec2i::zero(), vel: Vec2i::zero(), force: Vec2i::zero(), hp: MAX_HP, frag_count: 0,
+      pos: Vec2i::zero(), vel: Vec2i::zero(), force: Vec2i::zero(), hp: MAX_HP, upgrades: UpgradeLedger::default(),
       now_state: State::Idle, old_state: State::Idle, evt_death_clock: Clock::new(80),
-      move_frames: 0, jump_frames: 0, fly_frames: 0, inv_frames: 0, death_frames: 0,
+      move_frames: 0, jump_frames: 0, fly_frames: 0, stamina: PHYSICS_DEFAULT.funbari_time, inv_frames: 0, hurt_frames: 0, death_frames: 0, breath_frames: 0, breath_cooldown: 0, riding: None, drop_frames: 0, on_oneway: false, surface: Surface::Normal, slide_frames: 0, wall_dir: 0, wall_jump_frames: 0, can_wall_jump: false,
+      dash_frames: 0, dash_dir: Vec2i::zero(), can_dash: true, afterimages: [None; AFTERIMAGE_NUM], afterimage_idx: 0, coyote_frames: 0, double_jump_ready: false, gravity_frames: 0, physics: PHYSICS_DEFAULT,
       is_jump: false, is_inv: false, is_death: false, on_ground: true, xflip: false, yflip: false, rot: false
     }
   }
-  fn check_damage(&mut self, tile1: Option<&Tile>, tile2: Option<&Tile>, id: TileId) {
+  // dirは吹き飛ぶ向き(針と反対側)
+  fn check_damage(&mut self, tile1: Option<&Tile>, tile2: Option<&Tile>, id: TileId, dir: Vec2i) {
     // お前は既に死んでいる
     if self.is_death || self.is_inv { return }
 
-    let mut damaged = false;
-    // 針の方向によってダメージが通るか判定
-    if let Some(t) = tile1 { if t.id == id { damaged = true } }
-    if let Some(t) = tile2 { if t.id == id { damaged = true } }
-    if damaged {
-      self.hp -= 1;
-      // 無敵になる
-      self.is_inv = true;
-    }
+    // 針の方向によってダメージが通るか判定(針の先端側と喰らい判定が重なったときだけ)
+    let hurtbox = self.get_hurtbox();
+    let damaged = [tile1, tile2].into_iter().flatten().filter(|t| t.id == id).any(|t| {
+      let Some(b) = t.id.get_damage_box() else { return false };
+      is_overlap(hurtbox, (t.get_global_pos_x() + b.x, t.get_global_pos_y() + b.y, b.w, b.h))
+    });
+    if damaged { self.take_damage(dir); }
+  }
+  // 敵の攻撃など、タイル以外からのダメージ
+  pub fn take_damage(&mut self, dir: Vec2i) {
+    if self.is_death || self.is_inv { return }
+    self.hp -= 1;
+    // 無敵になる
+    self.is_inv = true;
+    // のけぞって吹き飛ぶ
+    self.hurt_frames = self.physics.hurt_time;
+    self.force = Vec2i::new(dir.x * self.physics.knockback_x, dir.y * self.physics.knockback_y);
+    self.vel.y = 0;
+    self.dash_frames = 0;
+    self.breath_frames = 0;
+    self.is_jump = false;
+  }
+  // 横方向はsrc_xから離れる向き、縦方向は上(重力の反対)へ吹き飛ぶ
+  pub fn get_knockback_dir(&self, src_x: i16) -> Vec2i {
+    let x = if self.pos.x + DRAGON_WIDTH / 2 < src_x { -1 } else { 1 };
+    Vec2i::new(x, -self.get_gravity())
+  }
+  fn is_hurt(&self) -> bool { self.hurt_frames > 0 }
+  // 喰らい判定(x, y, w, h) 重力反転中は足元が上になる
+  pub fn get_hurtbox(&self) -> (i16, i16, i16, i16) {
+    let y = if self.yflip { DRAGON_HEIGHT - HURTBOX.h - HURTBOX.y } else { HURTBOX.y };
+    (self.pos.x + HURTBOX.x, self.pos.y + y, HURTBOX.w, HURTBOX.h)
+  }
+  // 敵を踏んだときの跳ね返り
+  pub fn bounce(&mut self) {
+    self.vel.y = self.physics.jump_power;
+    self.jump_frames = 0;
+    self.fly_frames = 0;
+    self.stamina = self.get_max_stamina();
+    self.is_jump = false;
+    self.on_ground = false;
+  }
+  pub fn is_falling(&self) -> bool { !self.on_ground && self.vel.y > 0 }
+  pub fn get_max_hp(&self) -> u8 { MAX_HP + self.upgrades.get_level(Upgrade::MaxHp) }
+  // 飛行の体力(最大値は踏ん張りの強化で増える)
+  pub fn get_stamina(&self) -> u8 { self.stamina }
+  pub fn get_max_stamina(&self) -> u8 {
+    self.physics.funbari_time.saturating_add(self.upgrades.get_level(Upgrade::Flutter).saturating_mul(STAMINA_PER_LEVEL))
+  }
+  pub fn is_double_jump_ready(&self) -> bool { self.double_jump_ready }
+  fn refill_stamina(&mut self, amount: u8) {
+    self.stamina = self.stamina.saturating_add(amount).min(self.get_max_stamina());
   }
   fn check_death(&mut self) {
     if self.hp <= 0 {
@@ -115,6 +236,8 @@ impl Dragon {
     }
   }
   pub fn check_interactive(&mut self, tl: Option<&Tile>, tr: Option<&Tile>, bl: Option<&Tile>, br: Option<&Tile>) -> [Option<InteractiveCmd>; 4] {
+    // HPが満タンならハートは取らずに残す
+    let can_heal = self.hp < self.get_max_hp();
     // クロージャを使ってみたかっただけ
     let get_cmd = |t: Option<&Tile>| -> Option<InteractiveCmd> {
       if let Some(tile) = t {
@@ -122,18 +245,123 @@ impl Dragon {
           TileId::Key => { Some(InteractiveCmd::GetKey(tile.local_x, tile.local_y)) }
           TileId::Fragment => { Some(InteractiveCmd::GetFragment(tile.local_x, tile.local_y)) }
           TileId::GoalOpened => { Some(InteractiveCmd::ClearStage) }
+          TileId::Stamina => { Some(InteractiveCmd::GetStamina(tile.local_x, tile.local_y)) }
+          TileId::Checkpoint => { Some(InteractiveCmd::TouchCheckpoint(tile.local_x, tile.local_y)) }
+          TileId::Heart if can_heal => { Some(InteractiveCmd::GetHeart(tile.local_x, tile.local_y)) }
           _ => { None }
         }
       } else { None }
     };
 
-    return [get_cmd(tl), get_cmd(tr), get_cmd(bl), get_cmd(br)]
+    // 重力反転ゾーン
+    for flip in [tl, tr, bl, br].into_iter().flatten().filter_map(|t| t.id.get_gravity_flip()) {
+      if self.yflip == flip { continue }
+      self.set_gravity_flip(flip);
+      tone(300 | (600 << 16), 12, 40, TONE_TRIANGLE);
+    }
+    // ハート
+    if can_heal && [tl, tr, bl, br].into_iter().flatten().any(|t| t.id == TileId::Heart) {
+      self.hp = (self.hp + HEART_HEAL).min(self.get_max_hp());
+      tone(660 | (990 << 16), 12, 50, TONE_PULSE1);
+    }
+    // 体力回復の玉
+    if [tl, tr, bl, br].into_iter().flatten().any(|t| t.id == TileId::Stamina) {
+      self.stamina = self.get_max_stamina();
+      tone(500 | (900 << 16), 10, 40, TONE_PULSE2);
+    }
+
+    let mut cmds = [get_cmd(tl), get_cmd(tr), get_cmd(bl), get_cmd(br)];
+    // 回復は1回分なので、取るハートも最初の1個だけ
+    let mut got_heart = false;
+    for cmd in cmds.iter_mut() {
+      if let Some(InteractiveCmd::GetHeart(..)) = cmd {
+        if got_heart { *cmd = None; }
+        got_heart = true;
+      }
+    }
+    return cmds
 
   }
-  pub fn check_collision_x(&mut self, tl: Option<&Tile>, tr: Option<&Tile>, bl: Option<&Tile>, br: Option<&Tile>) {
+  // ばねで強く飛ばされているときに重なった、ひび割れた壁を壊す
+  pub fn check_break(&self, tl: Option<&Tile>, tr: Option<&Tile>, bl: Option<&Tile>, br: Option<&Tile>) -> [Option<InteractiveCmd>; 4] {
+    let is_launched = self.force.x.abs() >= SPRING_BREAK_FORCE || self.force.y.abs() >= SPRING_BREAK_FORCE;
+    let get_cmd = |t: Option<&Tile>| -> Option<InteractiveCmd> {
+      match t {
+        Some(tile) if is_launched && tile.id == TileId::CrackedWall => { Some(InteractiveCmd::BreakWall(tile.local_x, tile.local_y)) }
+        _ => { None }
+      }
+    };
+    [get_cmd(tl), get_cmd(tr), get_cmd(bl), get_cmd(br)]
+  }
+  // 乗っている移動床と一緒に動く
+  pub fn carry(&mut self, platforms: &[MovingPlatform]) {
+    if let Some(p) = self.riding.and_then(|idx| platforms.get(idx)) {
+      self.pos.x += p.delta.x;
+      self.pos.y += p.delta.y;
+    }
+  }
+  // 重力の向き(反転中は-1)
+  fn get_gravity(&self) -> i16 { if self.yflip { -1 } else { 1 } }
+  // このフレームの縦の移動量(ワールド座標)
+  fn get_dy(&self) -> i16 { self.vel.y * self.get_gravity() + self.force.y }
+  // 足元のタイルの上端(空中ならNone)
+  pub fn get_feet_y(&self) -> Option<i16> {
+    if !self.on_ground { return None }
+    Some(if self.yflip { self.pos.y - 1 } else { self.pos.y + DRAGON_HEIGHT })
+  }
+  fn set_gravity_flip(&mut self, flip: bool) {
+    self.yflip = flip;
+    self.vel.y = 0;
+    self.on_ground = false;
+    self.coyote_frames = self.physics.coyote_time; // 反転直後に元の向きで跳べないように
+    self.riding = None;
+  }
+  // 踏ん張り(体力が尽きたら落ちる)
+  fn flutter(&mut self) {
+    self.fly_frames = self.fly_frames.wrapping_add(1);
+    if self.stamina > 0 {
+      self.stamina -= 1;
+      self.now_state = State::Fly;
+      // fly_framesが奇数時のみ踏ん張りで上昇
+      if self.fly_frames & 0b01 == 0b01 { self.vel.y = 0; } else { self.vel.y = 1; }
+    } else { self.now_state = State::Fall }
+  }
+  // 着地処理(タイルと移動床で共通、重力反転中はground_yが竜の上端になる)
+  fn land(&mut self, ground_y: i16) {
+    self.pos.y = if self.yflip { ground_y } else { ground_y - DRAGON_HEIGHT };
+    self.vel.y = 0;
+    self.fly_frames = 0;
+    self.jump_frames = 0;
+    self.refill_stamina(STAMINA_REFILL_SPD);
+    self.double_jump_ready = self.upgrades.has(Upgrade::DoubleJump);
+  }
+  // 上から落ちてきたときだけ乗れるすり抜け床(乗れるなら床の上端を返す)
+  // 重力反転中は乗れない
+  fn find_oneway(&self, bl: Option<&Tile>, br: Option<&Tile>) -> Option<i16> {
+    let dy = self.get_dy();
+    if dy < 0 || self.yflip || self.drop_frames > 0 { return None }
+    let tile = [bl, br].into_iter().flatten().find(|t| t.id == TileId::OneWay)?;
+    let tile_top = tile.get_global_pos_y();
+    // 前のフレームで床より上にいたか
+    if self.pos.y + DRAGON_HEIGHT - dy <= tile_top { Some(tile_top) } else { None }
+  }
+  // 上から落ちてきて乗れる移動床
+  fn find_platform(&self, platforms: &[MovingPlatform]) -> Option<usize> {
+    let dy = self.get_dy();
+    if dy < 0 || self.yflip { return None }
+    let dragon_btm = self.pos.y + DRAGON_HEIGHT;
+    platforms.iter().position(|p| {
+      let (x, y, w, _) = p.get_rect();
+      // 前のフレームで床より上にいたか(床が上がってきた分は許容する)
+      let was_above = dragon_btm - dy <= y + p.delta.y.abs();
+      self.pos.x < x + w && x < self.pos.x + DRAGON_WIDTH && dragon_btm >= y && was_above
+    })
+  }
+  pub fn check_collision_x(&mut self, tl: Option<&Tile>, tr: Option<&Tile>, bl: Option<&Tile>, br: Option<&Tile>, platforms: &[MovingPlatform]) {
+    self.wall_dir = 0;
     // 左移動時
     // ダメージ判定
-    self.check_damage(tl, bl, TileId::NeedleRight);
+    self.check_damage(tl, bl, TileId::NeedleRight, Vec2i::new(1, 0));
     // 壁となるタイルかどうか判定
     if is_solid_tile(tl) || is_solid_tile(bl) {
       // 壁判定
@@ -143,16 +371,18 @@ impl Dragon {
         self.pos.x = tile_right;
         self.vel.x = 0;
       }
+      // 空中で壁に向かって押していれば張り付く
+      if self.can_wall_jump && !self.on_ground && is_pressed(BTN_LEFT) { self.wall_dir = -1; }
     }
     // ばね判定
     let mut bounced = false;
     if let Some(t) = tl { if t.id == TileId::SpringHori { bounced = true } }
     if let Some(t) = bl { if t.id == TileId::SpringHori { bounced = true } }
-    if bounced { self.force.x = SPRING_FORCE_X }
+    if bounced { self.force.x = self.physics.spring_force_x; self.dash_frames = 0; }
 
     // 右移動時
     // ダメージ判定
-    self.check_damage(tr, br, TileId::NeedleLeft);
+    self.check_damage(tr, br, TileId::NeedleLeft, Vec2i::new(-1, 0));
     // 壁判定
     if is_solid_tile(tr) || is_solid_tile(br) {
       let tile = tr.unwrap();
@@ -161,77 +391,130 @@ impl Dragon {
         self.pos.x = tile_left - DRAGON_WIDTH;
         self.vel.x = 0;
       }
+      if self.can_wall_jump && !self.on_ground && is_pressed(BTN_RIGHT) { self.wall_dir = 1; }
     }
     // ばね判定
     let mut bounced = false;
     if let Some(t) = tr { if t.id == TileId::SpringHori { bounced = true } }
     if let Some(t) = br { if t.id == TileId::SpringHori { bounced = true } }
-    if bounced { self.force.x = -SPRING_FORCE_X }
-
-
-
+    if bounced { self.force.x = -self.physics.spring_force_x; self.dash_frames = 0; }
+
+    // 側面も固い移動床は横から押し出す
+    for p in platforms.iter().filter(|p| p.is_solid_all()) {
+      if !p.is_overlap(self.pos.x, self.pos.y, DRAGON_WIDTH, DRAGON_HEIGHT) { continue }
+      let (x, _, w, _) = p.get_rect();
+      if self.pos.x + DRAGON_WIDTH / 2 < x + w / 2 { self.pos.x = x - DRAGON_WIDTH; }
+      else { self.pos.x = x + w; }
+      self.vel.x = 0;
+    }
   }
-  pub fn check_collision_y(&mut self, tl: Option<&Tile>, tr: Option<&Tile>, bl: Option<&Tile>, br: Option<&Tile>) {
-    // 落下時
-
-    // ダメージ判定
-    self.check_damage(bl, br, TileId::NeedleUp);
-    // ばね判定
-    let mut bounced = false;
-    if let Some(t) = bl { if t.id == TileId::SpringVert { bounced = true } }
-    if let Some(t) = br { if t.id == TileId::SpringVert { bounced = true } }
-    if bounced { self.force.y = -SPRING_FORCE_Y }
-
-    if is_solid_tile(bl) || is_solid_tile(br) {
-      // 壁判定
-      let tile = bl.unwrap();
-      let tile_top = tile.get_global_pos_y();
-      let dragon_btm = self.pos.y + DRAGON_HEIGHT;
-      if dragon_btm >= tile_top {
-        // 着地処理
-        self.pos.y = tile_top - DRAGON_HEIGHT;
-        self.vel.y = 0;
-        self.fly_frames = 0;
-        self.jump_frames = 0;
-        self.on_ground = true && !bounced; // ばねは接地しない
-      }
+  pub fn check_collision_y(&mut self, tl: Option<&Tile>, tr: Option<&Tile>, bl: Option<&Tile>, br: Option<&Tile>, platforms: &[MovingPlatform]) {
+    self.riding = None;
+    self.on_oneway = false;
+    self.surface = Surface::Normal;
+    // 足元と頭上のタイル(重力反転中は上下が入れ替わる)
+    let (feet, head) = if self.yflip { ([tl, tr], [bl, br]) } else { ([bl, br], [tl, tr]) };
+
+    // ダメージ判定(針の向きで決まるので重力には関係ない)
+    self.check_damage(bl, br, TileId::NeedleUp, Vec2i::new(0, -1));
+    self.check_damage(tl, tr, TileId::NeedleDown, Vec2i::new(0, 1));
+    // ばね判定(下のばねは上へ、上のばねは下へ弾く)
+    let spring_btm = is_spring_vert(bl) || is_spring_vert(br);
+    let spring_top = is_spring_vert(tl) || is_spring_vert(tr);
+    let bounced = if self.yflip { spring_top } else { spring_btm };
+
+    // 着地時
+    if let Some(tile) = feet.into_iter().flatten().find(|t| t.id.is_solid()) {
+      // 床の面(重力反転中はタイルの下端)
+      let ground_y = if self.yflip { tile.get_global_pos_y() + STAGE_TILE_SIZE as i16 } else { tile.get_global_pos_y() };
+      self.land(ground_y);
+      self.on_ground = !bounced; // ばねは接地しない
+      self.surface = get_surface(feet[0], feet[1]);
+    }
+    // すり抜け床への着地
+    else if let Some(tile_top) = self.find_oneway(bl, br) {
+      self.land(tile_top);
+      self.on_ground = true;
+      self.on_oneway = true;
+    }
+    // 移動床への着地
+    else if let Some(idx) = self.find_platform(platforms) {
+      self.land(platforms[idx].get_rect().1);
+      self.riding = Some(idx);
+      self.on_ground = true;
     // 着地処理
     } else { self.on_ground = false; }
 
-
-
-    // 上昇時
-
-    // ダメージ判定
-    self.check_damage(tl, tr, TileId::NeedleDown);
-
-    if is_solid_tile(tl) || is_solid_tile(tr) {
-      // 壁判定
-      let tile = tl.unwrap();
-      let tile_btm = tile.get_global_pos_y() + DRAGON_HEIGHT;
-      let dragon_top = self.pos.y;
-      if dragon_top < tile_btm {
-        // 頭打ち
-        self.pos.y = tile_btm;
+    // 頭打ち
+    if let Some(tile) = head.into_iter().flatten().find(|t| t.id.is_solid()) {
+      self.pos.y = if self.yflip { tile.get_global_pos_y() - DRAGON_HEIGHT } else { tile.get_global_pos_y() + STAGE_TILE_SIZE as i16 };
+      self.vel.y = 0;
+      self.force.y = 0;
+    }
+    // 側面も固い移動床には頭をぶつける
+    if !self.yflip && self.get_dy() < 0 {
+      for p in platforms.iter().filter(|p| p.is_solid_all()) {
+        if !p.is_overlap(self.pos.x, self.pos.y, DRAGON_WIDTH, DRAGON_HEIGHT) { continue }
+        let (_, y, _, h) = p.get_rect();
+        self.pos.y = y + h;
         self.vel.y = 0;
         self.force.y = 0;
       }
     }
-    // ばね判定
-    let mut bounced = false;
-    if let Some(t) = tl { if t.id == TileId::SpringVert { bounced = true } }
-    if let Some(t) = tr { if t.id == TileId::SpringVert { bounced = true } }
-    if bounced { self.force.y = SPRING_FORCE_Y }
 
+    // 頭打ちで勢いが消えた後に弾く
+    if spring_btm { self.force.y = -self.physics.spring_force_y; self.dash_frames = 0; }
+    if spring_top { self.force.y = self.physics.spring_force_y; self.dash_frames = 0; }
+    // 着地したらダッシュが回復する
+    if self.on_ground {
+      self.can_dash = true;
+      self.dash_frames = 0;
+      self.coyote_frames = 0;
+    } else { self.coyote_frames = self.coyote_frames.saturating_add(1); }
+  }
+
+  // 空中でZを押しながらX
+  fn is_dash_input(&self) -> bool { !self.is_hurt() && !self.on_ground && is_pressed(BTN_Z) && is_just_pressed(BTN_X) }
+  fn get_dash_spd(&self) -> i16 {
+    if self.dash_dir.x != 0 && self.dash_dir.y != 0 { DASH_DIAG_SPD } else { DASH_SPD }
+  }
+  // 十字キーの方向(押していなければ向いている方向)へダッシュする
+  fn start_dash(&mut self) {
+    let mut dir = Vec2i::zero();
+    if is_pressed(BTN_RIGHT) { dir.x += 1; }
+    if is_pressed(BTN_LEFT)  { dir.x -= 1; }
+    if is_pressed(BTN_DOWN)  { dir.y += 1; }
+    if is_pressed(BTN_UP)    { dir.y -= 1; }
+    if dir == Vec2i::zero() { dir.x = if self.xflip { -1 } else { 1 }; }
+    self.dash_dir = dir;
+    self.dash_frames = DASH_TIME;
+    self.can_dash = false;
+    self.fly_frames = 0;
+    self.is_jump = false;
+    tone(700 | (200 << 16), 8, 40, TONE_NOISE);
   }
 
   pub fn update_x(&mut self) {
     // ----------------
     // X軸の処理
     // ----------------
+    let prev_vel_x = self.vel.x;
     self.vel.x = 0;
     if self.force.x < 0 { self.force.x += 1 }
     else if self.force.x > 0 { self.force.x -= 1 }
+    // ダッシュ中は入力を受け付けない
+    if self.dash_frames == 0 && self.can_dash && self.is_dash_input() { self.start_dash(); }
+    if self.dash_frames > 0 {
+      self.vel.x = self.dash_dir.x * self.get_dash_spd();
+      self.pos.x += self.vel.x + self.force.x;
+      return
+    }
+    self.apply_conveyor();
+    // 被弾直後は操作できない
+    if self.is_hurt() {
+      self.pos.x += self.force.x;
+      return
+    }
     // 入力を取得
     if is_pressed(BTN_RIGHT) { self.vel.x += 1; }
     if is_pressed(BTN_LEFT)  { self.vel.x -= 1; }
@@ -241,8 +524,8 @@ impl Dragon {
       self.xflip = self.vel.x < 0; // 反転
       
       self.move_frames += 1;
-      // 4fに一度の加速処理
-      if self.move_frames >= 3 { 
+      // 数fに一度の加速処理
+      if self.move_frames >= self.physics.walk_accel_wait { 
         if self.vel.x > 0 { self.vel.x += 1; }
         else { self.vel.x -= 1; }
         self.move_frames = 0;
@@ -250,68 +533,153 @@ impl Dragon {
     } else {
       self.move_frames = 0;
       self.now_state = State::Idle;
+      self.slide_on_ice(prev_vel_x);
     }
 
     // 座標を更新
     self.pos.x += self.vel.x + self.force.x;
   }
+  // ベルトコンベアに押し流される(ばねの勢いの方が強ければそちら優先)
+  fn apply_conveyor(&mut self) {
+    if let Surface::Conveyor(push) = self.surface {
+      if self.force.x.abs() <= push.abs() { self.force.x = push; }
+    }
+  }
+  // 氷の上では少しずつしか止まれない
+  fn slide_on_ice(&mut self, prev_vel_x: i16) {
+    if self.surface == Surface::Ice && prev_vel_x != 0 {
+      self.vel.x = prev_vel_x;
+      self.slide_frames += 1;
+      if self.slide_frames >= ICE_SLIDE_TIME {
+        self.vel.x -= prev_vel_x.signum();
+        self.slide_frames = 0;
+      }
+    } else { self.slide_frames = 0; }
+  }
 
   pub fn update_y(&mut self) {
     // --------
     // Y軸の処理
     // --------
-    self.vel.y += 1;
     if self.force.y < 0 { self.force.y += 1 }
     else if self.force.y > 0 { self.force.y -= 1 }
+    // ダッシュ中は重力を無視する
+    if self.dash_frames > 0 {
+      self.dash_frames -= 1;
+      self.vel.y = if self.dash_frames > 0 { self.dash_dir.y * self.get_dash_spd() * self.get_gravity() } else { 0 };
+      self.now_state = State::Fly;
+      self.pos.y += self.get_dy();
+      return
+    }
+    self.gravity_frames += 1;
+    if self.gravity_frames >= self.physics.gravity_wait {
+      self.vel.y += 1;
+      self.gravity_frames = 0;
+    }
 
     // 入力を取得
-    if is_just_pressed(BTN_Z) && self.on_ground {
-      self.vel.y = JUMP_POWER;
+    if self.drop_frames > 0 { self.drop_frames -= 1; }
+    if self.wall_jump_frames > 0 { self.wall_jump_frames -= 1; }
+    // 着地前に押したジャンプも受け付ける(先行入力)
+    let is_jump_input = get_frames_since_pressed(BTN_Z) < self.physics.jump_buffer_time;
+    // 足場から離れた直後でもジャンプできる(コヨーテタイム)
+    let can_jump = self.on_ground || self.coyote_frames < self.physics.coyote_time;
+    // 被弾直後は操作できない
+    if self.is_hurt() {}
+    // 下+Zですり抜け床から降りる
+    else if is_just_pressed(BTN_Z) && is_pressed(BTN_DOWN) && self.on_oneway {
+      consume_press(BTN_Z);
+      self.drop_frames = ONEWAY_DROP_TIME;
+      self.on_ground = false;
+      self.on_oneway = false; }
+    else if is_jump_input && can_jump {
+      consume_press(BTN_Z);
+      self.vel.y = self.physics.jump_power;
       self.is_jump = true;
+      self.jump_frames = 0;
+      self.coyote_frames = self.physics.coyote_time;
       self.on_ground = false; }
+    // 壁ジャンプ(壁と反対側へ押し出す)
+    else if is_just_pressed(BTN_Z) && self.wall_dir != 0 {
+      consume_press(BTN_Z);
+      self.vel.y = self.physics.jump_power;
+      self.is_jump = true;
+      self.jump_frames = 0;
+      self.force.x = -self.wall_dir * WALL_JUMP_FORCE;
+      self.xflip = self.wall_dir > 0;
+      self.wall_jump_frames = WALL_JUMP_TIME;
+      self.wall_dir = 0; }
+    // 二段ジャンプ
+    else if is_just_pressed(BTN_Z) && self.double_jump_ready && !self.on_ground {
+      consume_press(BTN_Z);
+      self.vel.y = self.physics.jump_power;
+      self.is_jump = true;
+      self.jump_frames = 0;
+      self.double_jump_ready = false;
+      tone(400 | (800 << 16), 6, 40, TONE_PULSE1); }
 
     // 空中の挙動
     if !self.on_ground {
+      // 壁ずり
+      if self.wall_dir != 0 && self.vel.y > 0 {
+        if self.vel.y > WALL_SLIDE_SPD { self.vel.y = WALL_SLIDE_SPD; }
+        self.fly_frames = 0;
+        self.refill_stamina(STAMINA_REFILL_SPD);
+        self.now_state = State::WallSlide;
+      }
       // 降下中
-      if self.vel.y > 0 {
+      else if self.vel.y > 0 {
         // 速度制限
-        if self.vel.y >= MAX_FALL_SPD_Y { self.vel.y = MAX_FALL_SPD_Y; }
+        if self.vel.y >= self.physics.max_fall_spd_y { self.vel.y = self.physics.max_fall_spd_y; }
         // 踏ん張り
-        if is_pressed(BTN_Z) {
-          self.fly_frames += 1;
-          if self.fly_frames <= FUNBARI_TIME {
-            self.now_state = State::Fly;
-            // fly_framesが奇数時のみ踏ん張りで上昇
-            if self.fly_frames & 0b01 == 0b01 { self.vel.y = 0; } else { self.vel.y = 1; }
-          } else { self.now_state = State::Fall }
-
+        if is_pressed(BTN_Z) && !self.is_hurt() { self.flutter(); }
         // 通常落下
-        } else { self.now_state = State::Fall; }
+        else { self.now_state = State::Fall; }
       }
       // 上昇中
       else {
-        self.now_state = State::Jump;
-        if is_pressed(BTN_Z) { self.jump_frames += 1; }
+        self.now_state = if self.wall_jump_frames > 0 { State::WallJump } else { State::Jump };
+        if is_pressed(BTN_Z) && !self.is_hurt() { self.jump_frames += 1; }
         else { self.jump_frames = 0; self.is_jump = false; }
 
-        if self.jump_frames <= 10 && self.is_jump { self.vel.y -= 1; }
+        if self.jump_frames <= self.physics.jump_hold_time && self.is_jump { self.vel.y -= 1; }
       }
 
     }
 
-    // 座標を更新
-    self.pos.y += self.vel.y + self.force.y;
+    if self.is_hurt() { self.now_state = State::Hurt; }
+
+    // 座標を更新(速度は重力の向きが基準)
+    self.pos.y += self.get_dy();
+  }
+
+  // ブレス攻撃(吐いたら弾の座標と速度を返す)
+  pub fn update_breath(&mut self) -> Option<(Vec2i, Vec2i)> {
+    if self.breath_cooldown > 0 { self.breath_cooldown -= 1; }
+    if self.breath_frames > 0 {
+      self.breath_frames -= 1;
+      self.now_state = State::Breath;
+    }
+    if !is_just_pressed(BTN_X) || self.breath_cooldown > 0 || self.is_dash_input() || self.is_hurt() { return None }
+
+    self.breath_cooldown = BREATH_COOLDOWN;
+    self.breath_frames = BREATH_MOTION_TIME;
+    self.now_state = State::Breath;
+    // 向いている方向へ吐く
+    let (x, dir) = if self.xflip { (self.pos.x - 6, -1) } else { (self.pos.x + DRAGON_WIDTH - 2, 1) };
+    Some((Vec2i::new(x, self.pos.y + 4), Vec2i::new(dir * BREATH_SPD, 0)))
   }
 
   pub fn update(&mut self) {
     // 無敵時間さん
     if self.is_inv {
       self.inv_frames += 1;
-      if self.inv_frames > INV_TIME {
+      if self.inv_frames > self.physics.inv_time {
         self.inv_frames = 0;
         self.is_inv = false;
       }
     }
+    if self.hurt_frames > 0 { self.hurt_frames -= 1; }
 
     // アニメーション更新
     if self.now_state != self.old_state {
@@ -320,6 +688,18 @@ impl Dragon {
     self.anim[self.now_state as usize].play();
     self.old_state = self.now_state;
 
+    // 残像(ダッシュ中は2fごとに今の絵を置いていく)
+    for a in self.afterimages.iter_mut().flatten() { a.life = a.life.saturating_sub(1); }
+    if self.dash_frames > 0 && self.dash_frames.is_multiple_of(2) {
+      self.afterimages[self.afterimage_idx] = Some(AfterImage {
+        image: self.anim[self.now_state as usize].get_image(),
+        pos: self.pos,
+        flags: self.get_blit_flags(),
+        life: AFTERIMAGE_LIFE,
+      });
+      self.afterimage_idx = (self.afterimage_idx + 1) % AFTERIMAGE_NUM;
+    }
+
     self.check_death();
   }
 
@@ -330,20 +710,32 @@ impl Dragon {
     self.anim[State::Death as usize].play();
     let dx = self.vel.x + self.force.x;
     let mut dy = self.vel.y + self.force.y;
-    if dy > MAX_FALL_SPD_Y { dy = MAX_FALL_SPD_Y }
+    if dy > self.physics.max_fall_spd_y { dy = self.physics.max_fall_spd_y }
     self.pos.x += dx;
     self.pos.y += dy;
   }
 
   pub fn draw(&self, offset_x: i16, offset_y: i16) {
     // 無敵の点滅処理(2fに一回描画をパスする)
-    if self.is_inv && self.inv_frames & 0b10 == 0b10 { return; }
+    if self.is_inv && !self.is_hurt() && self.inv_frames & 0b10 == 0b10 { return; }
 
+    // 残像は1fおきに描画して薄く見せる
+    for a in self.afterimages.iter().flatten().filter(|a| a.life > 0 && a.life & 0b01 == 0) {
+      a.image.drawf((a.pos.x + offset_x) as i32, (a.pos.y + offset_y) as i32, a.flags);
+    }
+    let (x, y) = ((self.pos.x + offset_x) as i32, (self.pos.y + offset_y) as i32);
+    // 被弾直後は色を反転させて点滅する
+    if self.is_hurt() && self.hurt_frames & 0b10 == 0b10 {
+      self.anim[self.now_state as usize].get_image().drawfc(x, y, self.get_blit_flags(), HURT_FLASH_COL);
+    }
+    else { self.anim[self.now_state as usize].drawf(x, y, self.get_blit_flags()); }
+  }
+  fn get_blit_flags(&self) -> u32 {
     let mut flag: u32 = 0;
     if self.xflip { flag |= BLIT_FLIP_X }
     if self.yflip { flag |= BLIT_FLIP_Y }
     if self.rot   { flag |= BLIT_ROTATE }
-    self.anim[self.now_state as usize].drawf((self.pos.x + offset_x) as i32, (self.pos.y + offset_y) as i32, flag);
+    flag
   }
 
   pub fn draw_death(&self, offset_x: i16, offset_y: i16) {
@@ -353,4 +745,143 @@ impl Dragon {
     }
   }
 
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn tile(id: TileId) -> Tile { Tile { id, local_x: 0, local_y: 0 } }
+
+  // 片足でもコンベアや氷に乗っていればその性質になる
+  #[test]
+  fn surface_prefers_special_tile() {
+    let (wall, ice, conveyor) = (tile(TileId::Wall), tile(TileId::Ice), tile(TileId::ConveyorLeft));
+    assert_eq!(get_surface(Some(&wall), Some(&wall)), Surface::Normal);
+    assert_eq!(get_surface(Some(&wall), Some(&ice)), Surface::Ice);
+    assert!(matches!(get_surface(None, Some(&conveyor)), Surface::Conveyor(push) if push < 0));
+  }
+
+  // コンベアはばねの勢いより弱ければ上書きしない
+  #[test]
+  fn conveyor_pushes_unless_launched() {
+    let mut dragon = Dragon::new();
+    dragon.surface = TileId::ConveyorRight.get_surface();
+    let Surface::Conveyor(push) = dragon.surface else { panic!("not a conveyor") };
+    dragon.apply_conveyor();
+    assert_eq!(dragon.force.x, push);
+    dragon.force.x = -push * 4;
+    dragon.apply_conveyor();
+    assert_eq!(dragon.force.x, -push * 4);
+  }
+
+  // 重力反転中は落下・足元・喰らい判定・吹き飛ぶ向きが上下逆になる
+  #[test]
+  fn gravity_zone_flips_vertical_rules() {
+    let mut dragon = Dragon::new();
+    dragon.pos = Vec2i::new(32, 32);
+    assert_eq!(TileId::GravityUp.get_gravity_flip(), Some(true));
+    assert_eq!(TileId::Wall.get_gravity_flip(), None);
+    dragon.set_gravity_flip(TileId::GravityUp.get_gravity_flip().unwrap());
+    // 反転直後にその場で跳べないように
+    assert!(!dragon.on_ground && dragon.coyote_frames >= dragon.physics.coyote_time);
+    dragon.vel.y = 2;
+    assert_eq!(dragon.get_dy(), -2);
+    dragon.on_ground = true;
+    assert_eq!(dragon.get_feet_y(), Some(31));
+    assert_eq!(dragon.get_hurtbox().1, 32);
+    assert_eq!(dragon.get_knockback_dir(0).y, 1);
+    dragon.set_gravity_flip(TileId::GravityDown.get_gravity_flip().unwrap());
+    assert_eq!(dragon.get_dy(), 0);
+    assert_eq!(dragon.get_knockback_dir(0).y, -1);
+  }
+
+  // 踏ん張ると体力が減り、尽きたら落ちる。着地すると少しずつ戻る
+  #[test]
+  fn flutter_drains_stamina() {
+    let mut dragon = Dragon::new();
+    let max = dragon.get_max_stamina();
+    for _ in 0..max { dragon.flutter(); }
+    assert_eq!(dragon.get_stamina(), 0);
+    assert!(dragon.now_state == State::Fly);
+    dragon.flutter();
+    assert!(dragon.now_state == State::Fall);
+    dragon.refill_stamina(STAMINA_REFILL_SPD);
+    assert_eq!(dragon.get_stamina(), STAMINA_REFILL_SPD);
+    dragon.refill_stamina(u8::MAX);
+    assert_eq!(dragon.get_stamina(), max);
+  }
+
+  // 踏ん張りの強化で体力の最大値が増える
+  #[test]
+  fn flutter_upgrade_raises_max_stamina() {
+    let mut dragon = Dragon::new();
+    let base = dragon.get_max_stamina();
+    dragon.upgrades.deposit(0, 0b1);
+    assert!(dragon.upgrades.buy(Upgrade::Flutter));
+    assert_eq!(dragon.get_max_stamina(), base + STAMINA_PER_LEVEL);
+  }
+
+  // すり抜け床は上から落ちてきたときだけ乗れる
+  #[test]
+  fn oneway_only_catches_from_above() {
+    let floor = Tile { id: TileId::OneWay, local_x: 0, local_y: 2 };
+    let top = floor.get_global_pos_y();
+    let mut dragon = Dragon::new();
+    // 前のフレームでは床より上にいた
+    dragon.pos.y = top - DRAGON_HEIGHT + 2;
+    dragon.vel.y = 3;
+    assert_eq!(dragon.find_oneway(Some(&floor), None), Some(top));
+    // 下から跳び上がってきた
+    dragon.vel.y = -3;
+    assert_eq!(dragon.find_oneway(Some(&floor), None), None);
+    dragon.pos.y = top - DRAGON_HEIGHT + 8;
+    dragon.vel.y = 3;
+    assert_eq!(dragon.find_oneway(Some(&floor), None), None);
+    // 下+Zで降りている間と、重力反転中は乗れない
+    dragon.pos.y = top - DRAGON_HEIGHT + 2;
+    dragon.drop_frames = 1;
+    assert_eq!(dragon.find_oneway(None, Some(&floor)), None);
+    dragon.drop_frames = 0;
+    dragon.yflip = true;
+    assert_eq!(dragon.find_oneway(None, Some(&floor)), None);
+  }
+
+  // ひび割れた壁はばねで強く飛ばされているときだけ壊せる
+  #[test]
+  fn spring_launch_breaks_cracked_wall() {
+    let wall = Tile { id: TileId::CrackedWall, local_x: 3, local_y: 4 };
+    let mut dragon = Dragon::new();
+    dragon.force.x = SPRING_BREAK_FORCE - 1;
+    assert_eq!(dragon.check_break(Some(&wall), None, None, None), [None; 4]);
+    dragon.force.x = SPRING_BREAK_FORCE;
+    assert_eq!(dragon.check_break(None, Some(&wall), None, None)[1], Some(InteractiveCmd::BreakWall(3, 4)));
+  }
+
+  // ハートは満タンなら取れず、重なっていても1個だけ取って1回分回復する
+  #[test]
+  fn heart_heals_once_and_not_at_full_hp() {
+    let (left, right) = (Tile { id: TileId::Heart, local_x: 1, local_y: 1 }, Tile { id: TileId::Heart, local_x: 2, local_y: 1 });
+    let mut dragon = Dragon::new();
+    assert_eq!(dragon.check_interactive(Some(&left), Some(&right), None, None), [None; 4]);
+    dragon.hp = 1;
+    let cmds = dragon.check_interactive(Some(&left), Some(&right), None, None);
+    assert_eq!(cmds, [Some(InteractiveCmd::GetHeart(1, 1)), None, None, None]);
+    assert_eq!(dragon.hp, 1 + HEART_HEAL);
+  }
+
+  // 氷の上では手を離しても ICE_SLIDE_TIME ごとに1ずつしか減速しない
+  #[test]
+  fn ice_slows_down_gradually() {
+    let mut dragon = Dragon::new();
+    dragon.surface = Surface::Ice;
+    for _ in 1..ICE_SLIDE_TIME { dragon.slide_on_ice(2); }
+    assert_eq!(dragon.vel.x, 2);
+    dragon.slide_on_ice(2);
+    assert_eq!(dragon.vel.x, 1);
+    // 普通の床ならすぐ止まる
+    dragon.surface = Surface::Normal;
+    dragon.vel.x = 0;
+    dragon.slide_on_ice(2);
+    assert_eq!(dragon.vel.x, 0);
+  }
+}
@@ -0,0 +1,301 @@
+use crate::wasm4::*;
+use crate::utils::*;
+use crate::assets::img::*;
+use crate::dragon::*;
+use crate::stage::*;
+
+// -------------------------------
+// エンティティ(敵や弾などの動くもの)
+// -------------------------------
+pub const MAX_ENTITY: usize = 32;
+const ENEMY_SIZE: i16 = 16;
+const FLYER_AMP: f32 = 16.0;        // 上下に揺れる幅
+const TURRET_FIRE_WAIT: u16 = 120;
+const TURRET_RANGE: Vec2i = Vec2i::new(96, 48); // 竜がこの範囲内にいるときだけ撃つ
+const TURRET_SHOT_LIFE: u16 = 300;
+const BREATH_LIFE: u16 = 14;       // 射程は短い
+const RUBBLE_LIFE: u16 = 16;
+const MAX_HIT_EVENT: usize = 8;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum EntityKind {
+  None, // 空きスロット
+  BossShot,
+  Walker,     // 床を往復する(壁と崖で折り返す)
+  Flyer,      // 波打ちながら飛ぶ
+  Turret,     // 竜のいる方へ弾を撃つ
+  TurretShot,
+  Breath,     // 竜の吐く炎
+  Rubble,     // 壁が崩れる演出(当たり判定なし)
+}
+impl EntityKind {
+  // 種類ごとの当たり判定
+  fn get_hitbox(self) -> Hitbox {
+    match self {
+      EntityKind::None | EntityKind::Rubble => Hitbox::new(0, 0, 0, 0),
+      EntityKind::BossShot   => Hitbox::new(1, 1, 6, 6),
+      EntityKind::Walker     => Hitbox::new(2, 5, 12, 10),
+      EntityKind::Flyer      => Hitbox::new(2, 3, 12, 9),
+      EntityKind::Turret     => Hitbox::new(2, 2, 12, 13),
+      EntityKind::TurretShot => Hitbox::new(2, 2, 4, 4),
+      EntityKind::Breath     => Hitbox::new(0, 1, 8, 6),
+    }
+  }
+  fn get_max_hp(self) -> u8 {
+    match self {
+      EntityKind::Turret => 2,
+      _ => 1,
+    }
+  }
+  // ブレスで倒せるもの
+  pub fn is_enemy(self) -> bool {
+    matches!(self, EntityKind::Walker | EntityKind::Flyer | EntityKind::Turret)
+  }
+  // ステージデータから出現したときの速度
+  pub fn get_spawn_vel(self) -> Vec2i {
+    match self {
+      EntityKind::Walker | EntityKind::Flyer => Vec2i::new(-1, 0),
+      _ => Vec2i::zero(),
+    }
+  }
+}
+// ステージデータの文字から出現させるエンティティ(敵などはここに追加する)
+const ENTITY_GLYPHS: &[(u8, EntityKind)] = &[
+  (b'w', EntityKind::Walker),
+  (b'b', EntityKind::Flyer),
+  (b't', EntityKind::Turret),
+];
+pub fn ascii_to_entity_kind(c: u8) -> Option<EntityKind> {
+  ENTITY_GLYPHS.iter().find(|g| g.0 == c).map(|g| g.1)
+}
+
+// 当たり判定(座標からの相対位置と大きさ)
+#[derive(Copy, Clone)]
+pub struct Hitbox {
+  pub x: i16,
+  pub y: i16,
+  pub w: i16,
+  pub h: i16,
+}
+impl Hitbox {
+  pub const fn new(x: i16, y: i16, w: i16, h: i16) -> Self {
+    Self { x, y, w, h }
+  }
+}
+
+#[derive(Copy, Clone)]
+pub struct Entity {
+  pub kind: EntityKind,
+  pub pos: Vec2i,
+  pub vel: Vec2i,
+  pub hitbox: Hitbox,
+  pub origin: Vec2i, // 出現した座標
+  pub frames: u16,   // 出現してからのフレーム数(周期処理が止まらないよう一周させる)
+  pub hp: u8,
+}
+impl Entity {
+  const fn empty() -> Self {
+    Self { kind: EntityKind::None, pos: Vec2i::zero(), vel: Vec2i::zero(), hitbox: Hitbox::new(0, 0, 0, 0), origin: Vec2i::zero(), frames: 0, hp: 0 }
+  }
+  pub fn is_active(&self) -> bool { self.kind != EntityKind::None }
+  pub fn kill(&mut self) { self.kind = EntityKind::None; }
+  // 矩形(x, y, w, h)と当たり判定が重なっているか
+  pub fn is_overlap(&self, x: i16, y: i16, w: i16, h: i16) -> bool {
+    is_overlap(self.get_rect(), (x, y, w, h))
+  }
+  pub fn get_rect(&self) -> (i16, i16, i16, i16) {
+    (self.pos.x + self.hitbox.x, self.pos.y + self.hitbox.y, self.hitbox.w, self.hitbox.h)
+  }
+  // ダメージを受ける(HPが0になったら消える)
+  pub fn damage(&mut self) {
+    self.hp = self.hp.saturating_sub(1);
+    if self.hp == 0 {
+      tone(400 | (80 << 16), 10, 60, TONE_NOISE);
+      self.kill();
+    }
+    else { tone(500 | (250 << 16), 6, 50, TONE_PULSE1); }
+  }
+
+  // ステージ側に伝える出来事があれば返す
+  pub fn update(&mut self, stage: &StageHandler, dragon_pos: Vec2i) -> Option<EntityEvent> {
+    self.frames = self.frames.wrapping_add(1);
+    match self.kind {
+      EntityKind::BossShot => {
+        self.pos.x += self.vel.x;
+        self.pos.y += self.vel.y;
+        // 壁・床に当たったら消える
+        let cx = self.pos.x + self.hitbox.x + self.hitbox.w / 2;
+        let cy = self.pos.y + self.hitbox.y + self.hitbox.h / 2;
+        if stage.is_solid_at(cx, cy) { self.kill(); }
+      }
+      EntityKind::Walker => {
+        if self.frames.is_multiple_of(2) {
+          // 進行方向の壁と足元の崖を調べる
+          let front_x = if self.vel.x < 0 { self.pos.x + self.hitbox.x - 1 } else { self.pos.x + self.hitbox.x + self.hitbox.w };
+          let is_wall = stage.get_tile_from_pos(front_x, self.pos.y + ENEMY_SIZE / 2).is_some_and(|t| t.id.is_solid());
+          let is_ledge = !stage.get_tile_from_pos(front_x, self.pos.y + ENEMY_SIZE).is_some_and(|t| t.id.is_solid());
+          if is_wall || is_ledge { self.vel.x = -self.vel.x; }
+          else { self.pos.x += self.vel.x; }
+        }
+      }
+      EntityKind::Flyer => {
+        if self.frames.is_multiple_of(2) {
+          let front_x = if self.vel.x < 0 { self.pos.x + self.hitbox.x - 1 } else { self.pos.x + self.hitbox.x + self.hitbox.w };
+          if stage.is_solid_at(front_x, self.pos.y + ENEMY_SIZE / 2) { self.vel.x = -self.vel.x; }
+          else { self.pos.x += self.vel.x; }
+        }
+        self.pos.y = self.origin.y + ((self.frames as f32 * 0.08).sin() * FLYER_AMP) as i16;
+      }
+      EntityKind::Turret => {
+        // 竜の方を向く
+        let dx = dragon_pos.x - self.pos.x;
+        self.vel.x = if dx < 0 { -1 } else { 1 };
+        let in_range = dx.abs() < TURRET_RANGE.x && (dragon_pos.y - self.pos.y).abs() < TURRET_RANGE.y;
+        if in_range && self.frames.is_multiple_of(TURRET_FIRE_WAIT) {
+          let x = if self.vel.x < 0 { self.pos.x - 4 } else { self.pos.x + ENEMY_SIZE - 4 };
+          tone(300 | (150 << 16), 6, 30, TONE_PULSE2);
+          return Some(EntityEvent::Spawn(EntityKind::TurretShot, Vec2i::new(x, self.pos.y + 3), Vec2i::new(self.vel.x, 0)))
+        }
+      }
+      EntityKind::TurretShot => {
+        self.pos.x += self.vel.x;
+        let cx = self.pos.x + self.hitbox.x + self.hitbox.w / 2;
+        let cy = self.pos.y + self.hitbox.y + self.hitbox.h / 2;
+        if stage.is_solid_at(cx, cy) || self.frames > TURRET_SHOT_LIFE { self.kill(); }
+      }
+      EntityKind::Breath => {
+        self.pos.x += self.vel.x;
+        if self.frames > BREATH_LIFE { self.kill(); return None }
+        // 先端が壁に当たったら消える
+        let front_x = if self.vel.x < 0 { self.pos.x + self.hitbox.x } else { self.pos.x + self.hitbox.x + self.hitbox.w - 1 };
+        let cy = self.pos.y + self.hitbox.y + self.hitbox.h / 2;
+        if stage.is_solid_at(front_x, cy) {
+          self.kill();
+          return Some(EntityEvent::HitTile(Vec2i::new(front_x, cy)))
+        }
+      }
+      EntityKind::Rubble => {
+        if self.frames > RUBBLE_LIFE { self.kill(); }
+      }
+      EntityKind::None => {}
+    }
+    None
+  }
+  // 竜と重なったときの処理
+  pub fn collide_dragon(&mut self, dragon: &mut Dragon) {
+    match self.kind {
+      EntityKind::BossShot | EntityKind::TurretShot => {
+        dragon.take_damage(dragon.get_knockback_dir(self.pos.x + self.hitbox.x + self.hitbox.w / 2));
+        self.kill();
+      }
+      // 針と同じく、触れるとダメージ(無敵時間あり)
+      EntityKind::Walker | EntityKind::Flyer | EntityKind::Turret => {
+        dragon.take_damage(dragon.get_knockback_dir(self.pos.x + self.hitbox.x + self.hitbox.w / 2));
+      }
+      EntityKind::Breath | EntityKind::Rubble | EntityKind::None => {}
+    }
+  }
+  fn draw(&self, offset_x: i16, offset_y: i16) {
+    let x = (self.pos.x + offset_x) as i32;
+    let y = (self.pos.y + offset_y) as i32;
+    // 画像は右向きなので、左へ進むときに反転する
+    let flag = if self.vel.x < 0 { BLIT_FLIP_X } else { 0 };
+    let frame = (self.frames / 8 % 2) as usize;
+    match self.kind {
+      EntityKind::BossShot   => IMG_BOSS_SHOT.draw(x, y),
+      EntityKind::Walker     => ANIM_ENEMY_WALKER[frame].drawf(x, y, flag),
+      EntityKind::Flyer      => ANIM_ENEMY_FLYER[frame].drawf(x, y, flag),
+      EntityKind::Turret     => IMG_ENEMY_TURRET_01.drawf(x, y, flag),
+      EntityKind::TurretShot => IMG_ENEMY_SHOT_01.draw(x, y),
+      EntityKind::Breath     => ANIM_BREATH[(self.frames / 4 % 2) as usize].drawf(x, y, flag),
+      EntityKind::Rubble     => ANIM_WALL_BREAK[(self.frames / 8).min(1) as usize].draw(x, y),
+      EntityKind::None => {}
+    }
+  }
+}
+
+// エンティティの更新結果
+pub enum EntityEvent {
+  Spawn(EntityKind, Vec2i, Vec2i), // (種類, 座標, 速度)で出現させる
+  HitTile(Vec2i),                  // この座標のタイルに当たった
+}
+
+// 弾が当たった相手
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum HitTarget {
+  Entity(EntityKind),
+  Tile(u8, u8), // タイル座標
+  Boss,
+}
+// そのフレームに弾が当たった記録(他の仕掛けから参照する)
+#[derive(Copy, Clone)]
+pub struct HitEvent {
+  pub shooter: EntityKind,
+  pub target: HitTarget,
+}
+
+// 固定長のエンティティ置き場
+pub struct EntityPool {
+  entities: [Entity; MAX_ENTITY],
+  hits: [Option<HitEvent>; MAX_HIT_EVENT],
+}
+impl EntityPool {
+  pub fn new() -> Self {
+    Self { entities: [Entity::empty(); MAX_ENTITY], hits: [None; MAX_HIT_EVENT] }
+  }
+  pub fn clear(&mut self) {
+    self.entities = [Entity::empty(); MAX_ENTITY];
+    self.clear_hits();
+  }
+  pub fn get(&self, idx: usize) -> Entity { self.entities[idx] }
+  pub fn set(&mut self, idx: usize, e: Entity) { self.entities[idx] = e; }
+  // 空いているスロットに出現させる(満杯なら出現しない)
+  pub fn spawn(&mut self, kind: EntityKind, pos: Vec2i, vel: Vec2i) -> bool {
+    match self.entities.iter_mut().find(|e| !e.is_active()) {
+      Some(e) => {
+        *e = Entity { kind, pos, vel, hitbox: kind.get_hitbox(), origin: pos, frames: 0, hp: kind.get_max_hp() };
+        true
+      }
+      None => { trace("entity pool is full"); false }
+    }
+  }
+  // 矩形と重なっているエンティティ(衝突判定は全てここを通す)
+  pub fn query_mut(&mut self, x: i16, y: i16, w: i16, h: i16) -> impl Iterator<Item = &mut Entity> {
+    self.entities.iter_mut().filter(move |e| e.is_active() && e.is_overlap(x, y, w, h))
+  }
+
+  pub fn clear_hits(&mut self) {
+    self.hits = [None; MAX_HIT_EVENT];
+  }
+  pub fn push_hit(&mut self, hit: HitEvent) {
+    match self.hits.iter_mut().find(|h| h.is_none()) {
+      Some(h) => { *h = Some(hit); }
+      None => { trace("hit log is full"); }
+    }
+  }
+  // このフレームに当たった弾の一覧
+  pub fn get_hits(&self) -> impl Iterator<Item = &HitEvent> {
+    self.hits.iter().flatten()
+  }
+  // ブレスと敵の当たり判定(ボスはプールの外にいるのでBoss側で見る)
+  pub fn resolve_attacks(&mut self) {
+    for i in 0..MAX_ENTITY {
+      let atk = self.entities[i];
+      if atk.kind != EntityKind::Breath { continue }
+      let (x, y, w, h) = atk.get_rect();
+      let hit_kind = self.query_mut(x, y, w, h).find(|e| e.kind.is_enemy()).map(|e| {
+        let kind = e.kind;
+        e.damage();
+        kind
+      });
+      if let Some(kind) = hit_kind {
+        self.entities[i].kill();
+        self.push_hit(HitEvent { shooter: atk.kind, target: HitTarget::Entity(kind) });
+      }
+    }
+  }
+  pub fn draw(&self, offset_x: i16, offset_y: i16) {
+    for e in self.entities.iter().filter(|e| e.is_active()) { e.draw(offset_x, offset_y); }
+  }
+}
+
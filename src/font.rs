@@ -0,0 +1,137 @@
+use crate::utils::*;
+use crate::assets::img::font::RAWIMG_FONT;
+
+// -------------------------------
+// ビットマップフォント
+// -------------------------------
+// アトラスにはASCII(0x20~0x7E)の後に以下の文字が16文字ずつ並んでいる
+// 濁点・半濁点付きの文字は元の文字に゛゜を重ねて描画する
+const FONT_CHARS_JA: &str = "ぁあぃいぅうぇえぉおかきくけこさしすせそたちっつてとなにぬねのはひふへほまみむめもゃやゅゆょよらりるれろわをん\
+                             ァアィイゥウェエォオカキクケコサシスセソタチッツテトナニヌネノハヒフヘホマミムメモャヤュユョヨラリルレロワヲン\
+                             、。「」・ー゛゜ⓍⓏ";
+const FONT_ASCII_COUNT: usize = 95;
+const FONT_GLYPH_COUNT: usize = FONT_ASCII_COUNT + 120;
+const FONT_ATLAS_COLS: usize = 16;
+const FONT_CELL: u32 = 8;
+const FONT_SPACE_W: u8 = 4;
+const FONT_MARK_X: i32 = 5; // 濁点・半濁点を重ねる位置
+pub const FONT_LINE_H: i32 = 10;
+// 行頭に来てはいけない文字
+const FONT_NO_BREAK_BEFORE: &str = "、。」・ー゛゜ぁぃぅぇぉっゃゅょァィゥェォッャュョ！？";
+
+// 各文字の幅(右端のピクセル+1pxの字間)。アトラスからコンパイル時に求める
+const FONT_WIDTHS: [u8; FONT_GLYPH_COUNT] = calc_glyph_widths(&RAWIMG_FONT);
+const fn calc_glyph_widths(raw: &RawImage) -> [u8; FONT_GLYPH_COUNT] {
+  let mut widths = [0; FONT_GLYPH_COUNT];
+  let mut i = 0;
+  while i < FONT_GLYPH_COUNT {
+    let ox = (i % FONT_ATLAS_COLS) as u32 * FONT_CELL;
+    let oy = (i / FONT_ATLAS_COLS) as u32 * FONT_CELL;
+    let mut w = 0;
+    let mut y = 0;
+    while y < FONT_CELL {
+      let mut x = 0;
+      while x < FONT_CELL {
+        let bit = (oy + y) * raw.width + ox + x;
+        if raw.data[(bit / 8) as usize] & (0x80 >> (bit % 8)) != 0 && x + 2 > w { w = x + 2; }
+        x += 1;
+      }
+      y += 1;
+    }
+    widths[i] = if w == 0 { FONT_SPACE_W } else { w as u8 };
+    i += 1;
+  }
+  widths
+}
+
+#[derive(Copy, Clone, PartialEq)]
+enum Mark {
+  None,
+  Dakuten,
+  Handakuten,
+}
+
+// 色違いのフォントは同じアトラスを別の描画色で持つ
+pub struct Font {
+  atlas: &'static Image,
+}
+impl Font {
+  pub const fn new(atlas: &'static Image) -> Self {
+    Self { atlas }
+  }
+  pub fn get_char_width(&self, c: char) -> i32 {
+    let (base, mark) = decompose(c);
+    let w = FONT_WIDTHS[get_glyph_idx(base)] as i32;
+    if mark != Mark::None { w.max(FONT_CELL as i32) } else { w }
+  }
+  // 最も長い行の幅
+  pub fn get_text_width(&self, msg: &str) -> i32 {
+    msg.split('\n').map(|line| line.chars().map(|c| self.get_char_width(c)).sum()).max().unwrap_or(0)
+  }
+  pub fn draw(&self, msg: &str, x: i32, y: i32) {
+    let mut cx = x;
+    let mut cy = y;
+    for c in msg.chars() {
+      if c == '\n' {
+        cx = x;
+        cy += FONT_LINE_H;
+        continue
+      }
+      let (base, mark) = decompose(c);
+      self.draw_glyph(get_glyph_idx(base), cx, cy);
+      match mark {
+        Mark::Dakuten    => self.draw_glyph(get_glyph_idx('゛'), cx + FONT_MARK_X, cy - 2),
+        Mark::Handakuten => self.draw_glyph(get_glyph_idx('゜'), cx + FONT_MARK_X, cy - 3),
+        Mark::None => {}
+      }
+      cx += self.get_char_width(c);
+    }
+  }
+  // 行ごとに中央揃えで描画
+  pub fn draw_center_x(&self, msg: &str, y: i32) {
+    for (i, line) in msg.split('\n').enumerate() {
+      let x = (160 - self.get_text_width(line)) / 2;
+      self.draw(line, x, y + i as i32 * FONT_LINE_H);
+    }
+  }
+  fn draw_glyph(&self, idx: usize, x: i32, y: i32) {
+    let sx = (idx % FONT_ATLAS_COLS) as u32 * FONT_CELL;
+    let sy = (idx / FONT_ATLAS_COLS) as u32 * FONT_CELL;
+    self.atlas.draw_sub(x, y, FONT_CELL, FONT_CELL, sx, sy);
+  }
+}
+
+// 文字cの直前で改行してよいか(日本語は文字単位で折り返す)
+pub fn can_break_before(c: char) -> bool {
+  c as u32 >= 0x3000 && !FONT_NO_BREAK_BEFORE.contains(c)
+}
+
+// アトラス上の番号(無い文字は'?')
+fn get_glyph_idx(c: char) -> usize {
+  let c = match c { '！' => '!', '？' => '?', '　' => ' ', _ => c };
+  if (' '..='~').contains(&c) { return c as usize - 0x20 }
+  match FONT_CHARS_JA.chars().position(|f| f == c) {
+    Some(i) => FONT_ASCII_COUNT + i,
+    None => '?' as usize - 0x20,
+  }
+}
+
+// 濁点・半濁点付きの文字を元の文字と記号に分解する
+fn decompose(c: char) -> (char, Mark) {
+  let code = c as u32;
+  // カタカナはひらがなと同じ並びなので、ひらがなに直して判定する
+  let kana_offset = if (0x30A1..=0x30F6).contains(&code) { 0x60 } else { 0 };
+  let hira = code - kana_offset;
+  let (base, mark) = match hira {
+    0x304C..=0x3062 if (hira - 0x304B) % 2 == 1 => (hira - 1, Mark::Dakuten),    // が~ぢ
+    0x3065..=0x3069 if (hira - 0x3064) % 2 == 1 => (hira - 1, Mark::Dakuten),    // づ~ど
+    0x3070..=0x307D => match (hira - 0x306F) % 3 {                               // ば~ぽ
+      1 => (hira - 1, Mark::Dakuten),
+      2 => (hira - 2, Mark::Handakuten),
+      _ => (hira, Mark::None),
+    },
+    0x3094 => (0x3046, Mark::Dakuten),                                           // ゔ
+    _ => (hira, Mark::None),
+  };
+  (char::from_u32(base + kana_offset).unwrap_or('?'), mark)
+}
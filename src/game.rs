@@ -1,6 +1,7 @@
 
 use crate::{scenes::*, utils::update_btn};
 use crate::wasm4::*;
+use crate::save::load_save;
 pub struct Game {
   scene: SceneHandler,
 }
@@ -10,6 +11,7 @@ impl Game {
     Self { scene: SceneHandler::new() }
   }
   pub fn start(&mut self) {
+    load_save();
     self.scene.start()
   }
 
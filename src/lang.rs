@@ -0,0 +1,190 @@
+use crate::save::*;
+
+// -------------------------------
+// 多言語対応
+// -------------------------------
+// 文字列は言語ごとの関数にmatchで持たせる
+// (StrIdを追加して訳を書き忘れるとコンパイルエラーになる)
+#[derive(Copy, Clone, PartialEq)]
+pub enum Lang {
+  En,
+  Ja,
+}
+impl Lang {
+  pub fn from_u8(n: u8) -> Option<Self> {
+    match n {
+      0 => Some(Lang::En),
+      1 => Some(Lang::Ja),
+      _ => None,
+    }
+  }
+  pub fn to_u8(self) -> u8 { self as u8 }
+  // タイトル画面で切り替えるときの次の言語
+  pub fn next(self) -> Self {
+    match self {
+      Lang::En => Lang::Ja,
+      Lang::Ja => Lang::En,
+    }
+  }
+}
+
+// 数値を埋め込む文字列は"{}"の位置に置き換える(tr_fmt)
+#[derive(Copy, Clone, PartialEq)]
+pub enum StrId {
+  // タイトル
+  TitleName,
+  TitlePressStart,
+  LangName,
+  // メイン
+  StageNum,
+  StageName1,
+  StageName2,
+  StageName3,
+  StageName4,
+  StageNameLastBattle,
+  HudFragment,
+  // 強化メニュー
+  UpgradeTitle,
+  UpgradeFragment,
+  UpgradeMaxHp,
+  UpgradeFlutter,
+  UpgradeDoubleJump,
+  UpgradeLevel,
+  UpgradeCost,
+  UpgradeMaxed,
+  UpgradeDone,
+  // カットシーン
+  Stage1Intro,
+  Stage3Intro,
+  LastBattleIntro,
+  LastBattleVictory,
+  EndingEscaped,
+  EndingFalse1,
+  EndingFalse2,
+  EndingFalse3,
+  EndingTrue1,
+  EndingTrue2,
+  EndingTrue3,
+  // スタッフロール
+  CreditStaff,
+  CreditProgram,
+  CreditGraphics,
+  CreditStageDesign,
+  CreditThanks,
+  CreditAndYou,
+  // エンディング
+  EndThanks,
+  EndPressTitle,
+  EndFragment,
+}
+
+fn str_en(id: StrId) -> &'static str {
+  match id {
+    StrId::TitleName           => "blood dragon",
+    StrId::TitlePressStart     => "press Ⓩ to start",
+    StrId::LangName            => "english",
+    StrId::StageNum            => "stage {}",
+    StrId::StageName1          => "the first room",
+    StrId::StageName2          => "needle maze",
+    StrId::StageName3          => "spring tower",
+    StrId::StageName4          => "the last gate",
+    StrId::StageNameLastBattle => "the last battle",
+    StrId::HudFragment         => "{}",
+    StrId::UpgradeTitle        => "- upgrade -",
+    StrId::UpgradeFragment     => "fragments: {}",
+    StrId::UpgradeMaxHp        => "max hp",
+    StrId::UpgradeFlutter      => "flutter",
+    StrId::UpgradeDoubleJump   => "double jump",
+    StrId::UpgradeLevel        => "{}/{}",
+    StrId::UpgradeCost         => "cost: {}",
+    StrId::UpgradeMaxed        => "maxed out",
+    StrId::UpgradeDone         => "next stage",
+    StrId::Stage1Intro         => "the gate is locked. i have to find the key to open it.",
+    StrId::Stage3Intro         => "the gate waits\nat the top.",
+    StrId::LastBattleIntro     => "something is coming\nfrom above...",
+    StrId::LastBattleVictory   => "the guardian fell.\nthe gate is open!",
+    StrId::EndingEscaped       => "the dragon escaped\nthe last gate.",
+    StrId::EndingFalse1        => "but its blood\nis still cold.",
+    StrId::EndingFalse2        => "some fragments of\nits heart remain...",
+    StrId::EndingFalse3        => "somewhere in\nthe dark towers.",
+    StrId::EndingTrue1         => "every fragment of\nits heart returned.",
+    StrId::EndingTrue2         => "blood runs hot\nthrough its wings.",
+    StrId::EndingTrue3         => "the blood dragon\nis whole again.",
+    StrId::CreditStaff         => "- staff -",
+    StrId::CreditProgram       => "program",
+    StrId::CreditGraphics      => "graphics",
+    StrId::CreditStageDesign   => "stage design",
+    StrId::CreditThanks        => "special thanks",
+    StrId::CreditAndYou        => "and you!",
+    StrId::EndThanks           => "thank you\nfor playing!",
+    StrId::EndPressTitle       => "press Ⓩ to title",
+    StrId::EndFragment         => "{}/{}",
+  }
+}
+
+fn str_ja(id: StrId) -> &'static str {
+  match id {
+    StrId::TitleName           => "ブラッド ドラゴン",
+    StrId::TitlePressStart     => "Ⓩで スタート",
+    StrId::LangName            => "にほんご",
+    StrId::StageNum            => "ステージ {}",
+    StrId::StageName1          => "はじまりの へや",
+    StrId::StageName2          => "とげの めいろ",
+    StrId::StageName3          => "ばねの とう",
+    StrId::StageName4          => "さいごの とびら",
+    StrId::StageNameLastBattle => "さいごの たたかい",
+    StrId::HudFragment         => "{}",
+    StrId::UpgradeTitle        => "- きょうか -",
+    StrId::UpgradeFragment     => "かけら: {}",
+    StrId::UpgradeMaxHp        => "さいだい HP",
+    StrId::UpgradeFlutter      => "ふんばり",
+    StrId::UpgradeDoubleJump   => "にだん ジャンプ",
+    StrId::UpgradeLevel        => "{}/{}",
+    StrId::UpgradeCost         => "ひつような かけら: {}",
+    StrId::UpgradeMaxed        => "これいじょう つよく できない",
+    StrId::UpgradeDone         => "つぎの ステージへ",
+    StrId::Stage1Intro         => "とびらに かぎが かかっている。かぎを さがさなきゃ。",
+    StrId::Stage3Intro         => "とびらは\nとうの てっぺんだ。",
+    StrId::LastBattleIntro     => "うえから なにかが\nやってくる...",
+    StrId::LastBattleVictory   => "まもりびとは たおれた。\nとびらが ひらいた!",
+    StrId::EndingEscaped       => "りゅうは さいごの\nとびらを ぬけた。",
+    StrId::EndingFalse1        => "けれど その ちは\nまだ つめたい。",
+    StrId::EndingFalse2        => "こころの かけらが\nまだ のこっている...",
+    StrId::EndingFalse3        => "くらい とうの\nどこかに。",
+    StrId::EndingTrue1         => "こころの かけらが\nすべて もどった。",
+    StrId::EndingTrue2         => "あつい ちが\nつばさを めぐる。",
+    StrId::EndingTrue3         => "ブラッドドラゴンは\nよみがえった。",
+    StrId::CreditStaff         => "- スタッフ -",
+    StrId::CreditProgram       => "プログラム",
+    StrId::CreditGraphics      => "グラフィック",
+    StrId::CreditStageDesign   => "ステージデザイン",
+    StrId::CreditThanks        => "スペシャルサンクス",
+    StrId::CreditAndYou        => "そして あなた!",
+    StrId::EndThanks           => "あそんでくれて\nありがとう!",
+    StrId::EndPressTitle       => "Ⓩで タイトルへ",
+    StrId::EndFragment         => "{}/{}",
+  }
+}
+
+// 現在の言語の文字列を取得
+pub fn tr(id: StrId) -> &'static str {
+  match get_lang() {
+    Lang::En => str_en(id),
+    Lang::Ja => str_ja(id),
+  }
+}
+// "{}"を数値で順に置き換えた文字列を取得
+pub fn tr_fmt(id: StrId, nums: &[u32]) -> String {
+  let mut out = String::new();
+  let mut nums = nums.iter();
+  for (i, part) in tr(id).split("{}").enumerate() {
+    if i > 0 {
+      match nums.next() {
+        Some(n) => out.push_str(&n.to_string()),
+        None => out.push_str("{}"),
+      }
+    }
+    out.push_str(part);
+  }
+  out
+}
@@ -1,12 +1,24 @@
-#[cfg(feature = "buddy-alloc")]
+#[cfg(all(feature = "buddy-alloc", not(test)))] // テストは標準のアロケータで動かす
 mod alloc;
 mod wasm4;
+#[cfg(test)]
+mod wasm4_stub;
 
 mod utils;
 mod assets;
+mod font;
+mod lang;
+mod save;
+mod upgrade;
 mod dragon;
+mod boss;
+mod entity;
+mod physics;
+mod platform;
 mod stage;
 mod scenes;
+mod cutscene;
+mod dialog;
 
 mod game;
 
@@ -0,0 +1,38 @@
+// -------------------------------
+// 竜の挙動パラメータ
+// -------------------------------
+// ステージごとに選べる(難易度やデバッグ画面から書き換えてもよい)
+#[derive(Copy, Clone, Debug)]
+pub struct PhysicsProfile {
+  pub jump_power: i16,
+  pub jump_hold_time: u8,    // Zを押し続けて高く跳べる時間
+  pub gravity_wait: u8,      // 何フレームに一度落下速度が増えるか
+  pub max_fall_spd_y: i16,
+  pub walk_accel_wait: u8,   // 何フレームに一度歩く速度が増えるか
+  pub funbari_time: u8,
+  pub inv_time: u8,
+  pub spring_force_x: i16,
+  pub spring_force_y: i16,
+  pub coyote_time: u8,       // 足場から離れた後もジャンプできる時間
+  pub jump_buffer_time: u16, // 着地前のジャンプ入力を覚えておく時間
+  pub knockback_x: i16,      // 被弾時に吹き飛ぶ勢い
+  pub knockback_y: i16,
+  pub hurt_time: u8,         // 被弾後に操作できない時間
+}
+
+pub const PHYSICS_DEFAULT: PhysicsProfile = PhysicsProfile {
+  jump_power: -4,
+  jump_hold_time: 10,
+  gravity_wait: 1,
+  max_fall_spd_y: 4,
+  walk_accel_wait: 3,
+  funbari_time: 60,
+  inv_time: 90,
+  spring_force_x: 12,
+  spring_force_y: 18,
+  coyote_time: 6,
+  jump_buffer_time: 6,
+  knockback_x: 4,
+  knockback_y: 4,
+  hurt_time: 16,
+};
@@ -0,0 +1,117 @@
+use crate::utils::*;
+use crate::assets::img::*;
+use crate::stage::STAGE_TILE_SIZE;
+
+// -------------------------------
+// 移動床
+// -------------------------------
+pub const PLATFORM_HEIGHT: i16 = 8;
+
+// 経路の辿り方
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum PathMode {
+  PingPong, // 端まで行ったら折り返す
+  Loop,     // 最後の地点から最初の地点へ戻る
+}
+
+// ステージに置く移動床の設定
+pub struct PlatformDef {
+  pub path: &'static [(u8, u8)], // 通過する地点(タイル座標)
+  pub mode: PathMode,
+  pub speed: i16,                // 1フレームに動くピクセル数
+  pub width: u8,                 // 幅(タイル数)
+  pub solid_all: bool,           // falseなら上面だけ乗れる
+}
+
+pub struct MovingPlatform {
+  def: &'static PlatformDef,
+  pub pos: Vec2i,
+  pub delta: Vec2i, // このフレームに動いた量(乗っている竜を運ぶ)
+  target: usize,    // 向かっている地点
+  is_back: bool,    // 折り返し中
+}
+// タイル座標をワールド座標へ
+fn path_pos(p: (u8, u8)) -> Vec2i {
+  Vec2i::new(p.0 as i16 * STAGE_TILE_SIZE as i16, p.1 as i16 * STAGE_TILE_SIZE as i16)
+}
+impl MovingPlatform {
+  pub fn new(def: &'static PlatformDef) -> Self {
+    Self { def, pos: path_pos(def.path[0]), delta: Vec2i::zero(), target: 1 % def.path.len(), is_back: false }
+  }
+  pub fn is_solid_all(&self) -> bool { self.def.solid_all }
+  // (x, y, w, h)
+  pub fn get_rect(&self) -> (i16, i16, i16, i16) {
+    (self.pos.x, self.pos.y, self.def.width as i16 * STAGE_TILE_SIZE as i16, PLATFORM_HEIGHT)
+  }
+  // 矩形(x, y, w, h)と重なっているか
+  pub fn is_overlap(&self, x: i16, y: i16, w: i16, h: i16) -> bool {
+    is_overlap(self.get_rect(), (x, y, w, h))
+  }
+
+  pub fn update(&mut self) {
+    let target = path_pos(self.def.path[self.target]);
+    let spd = self.def.speed;
+    let old = self.pos;
+    self.pos.x += (target.x - self.pos.x).clamp(-spd, spd);
+    self.pos.y += (target.y - self.pos.y).clamp(-spd, spd);
+    self.delta = Vec2i::new(self.pos.x - old.x, self.pos.y - old.y);
+    if self.pos == target { self.next_target(); }
+  }
+  fn next_target(&mut self) {
+    let len = self.def.path.len();
+    if len < 2 { return }
+    match self.def.mode {
+      PathMode::Loop => { self.target = (self.target + 1) % len; }
+      PathMode::PingPong => {
+        if self.target == len - 1 { self.is_back = true; }
+        else if self.target == 0 { self.is_back = false; }
+        if self.is_back { self.target -= 1; } else { self.target += 1; }
+      }
+    }
+  }
+
+  pub fn draw(&self, offset_x: i16, offset_y: i16) {
+    for i in 0..self.def.width as i16 {
+      let x = self.pos.x + i * STAGE_TILE_SIZE as i16 + offset_x;
+      IMG_PLATFORM_01.draw(x as i32, (self.pos.y + offset_y) as i32);
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const PING_PONG: PlatformDef = PlatformDef { path: &[(0, 0), (1, 0), (2, 0)], mode: PathMode::PingPong, speed: 4, width: 1, solid_all: false };
+  const LOOP: PlatformDef = PlatformDef { path: &[(0, 0), (1, 0), (1, 1)], mode: PathMode::Loop, speed: 4, width: 1, solid_all: true };
+
+  // 1マス進むのにかかるフレーム数ごとに、通過した地点を集める
+  fn visited(def: &'static PlatformDef, count: usize) -> Vec<Vec2i> {
+    let mut platform = MovingPlatform::new(def);
+    let step = STAGE_TILE_SIZE as i16 / def.speed;
+    (0..count).map(|_| {
+      for _ in 0..step { platform.update(); }
+      platform.pos
+    }).collect()
+  }
+
+  #[test]
+  fn ping_pong_turns_back_at_both_ends() {
+    let expected: Vec<Vec2i> = [1, 2, 1, 0, 1].iter().map(|x| path_pos((*x, 0))).collect();
+    assert_eq!(visited(&PING_PONG, 5), expected);
+  }
+
+  #[test]
+  fn loop_returns_to_first_point() {
+    let expected: Vec<Vec2i> = [(1, 0), (1, 1), (0, 0), (1, 0)].iter().map(|p| path_pos(*p)).collect();
+    assert_eq!(visited(&LOOP, 4), expected);
+  }
+
+  // 動いた量は乗っている竜を運ぶのに使う
+  #[test]
+  fn delta_matches_movement() {
+    let mut platform = MovingPlatform::new(&PING_PONG);
+    platform.update();
+    assert_eq!(platform.delta, Vec2i::new(PING_PONG.speed, 0));
+  }
+}
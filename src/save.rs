@@ -0,0 +1,70 @@
+use crate::wasm4::*;
+use crate::lang::*;
+use crate::upgrade::*;
+use lazy_static::lazy_static;
+use std::sync::Mutex;
+
+// -------------------------------
+// セーブデータ
+// -------------------------------
+lazy_static! {
+  static ref SAVE_DATA: Mutex<SaveData> = Mutex::new(SaveData::new());
+}
+const SAVE_MAGIC: [u8; 2] = *b"BD";
+const SAVE_VERSION: u8 = 2;
+const SAVE_SIZE_V1: usize = 4; // マジック(2) + バージョン(1) + 言語(1)
+const SAVE_SIZE: usize = SAVE_SIZE_V1 + UPGRADE_DATA_SIZE; // + 強化の台帳
+
+struct SaveData {
+  lang: Lang,
+  upgrades: UpgradeLedger,
+}
+impl SaveData {
+  fn new() -> Self {
+    Self { lang: Lang::En, upgrades: UpgradeLedger::default() }
+  }
+  fn to_bytes(&self) -> [u8; SAVE_SIZE] {
+    let mut buf = [0u8; SAVE_SIZE];
+    buf[0..SAVE_SIZE_V1].copy_from_slice(&[SAVE_MAGIC[0], SAVE_MAGIC[1], SAVE_VERSION, self.lang.to_u8()]);
+    buf[SAVE_SIZE_V1..].copy_from_slice(&self.upgrades.to_bytes());
+    buf
+  }
+  // 壊れている・未保存のデータなら初期値のまま
+  // (バージョン1のデータは言語だけ引き継ぐ、貯めた欠片の無い古いバージョン2は0で読む)
+  fn from_bytes(buf: &[u8; SAVE_SIZE]) -> Self {
+    let mut data = Self::new();
+    if buf[0..2] != SAVE_MAGIC || buf[2] == 0 || buf[2] > SAVE_VERSION { return data }
+    if let Some(lang) = Lang::from_u8(buf[3]) { data.lang = lang; }
+    if buf[2] >= 2 { data.upgrades = UpgradeLedger::from_bytes(&buf[SAVE_SIZE_V1..]); }
+    data
+  }
+  fn write(&self) {
+    let buf = self.to_bytes();
+    unsafe { diskw(buf.as_ptr(), SAVE_SIZE as u32); }
+  }
+}
+
+// ディスクから読み込む(起動時に一度だけ呼ぶ)
+pub fn load_save() {
+  let mut buf = [0u8; SAVE_SIZE];
+  let read_size = unsafe { diskr(buf.as_mut_ptr(), SAVE_SIZE as u32) };
+  if (read_size as usize) < SAVE_SIZE_V1 { return }
+  *SAVE_DATA.lock().expect("save_data") = SaveData::from_bytes(&buf);
+}
+pub fn get_lang() -> Lang {
+  SAVE_DATA.lock().expect("save_data").lang
+}
+// 変更したらすぐにディスクへ書き込む
+pub fn set_lang(lang: Lang) {
+  let mut data = SAVE_DATA.lock().expect("save_data");
+  data.lang = lang;
+  data.write();
+}
+pub fn get_upgrades() -> UpgradeLedger {
+  SAVE_DATA.lock().expect("save_data").upgrades
+}
+pub fn set_upgrades(upgrades: UpgradeLedger) {
+  let mut data = SAVE_DATA.lock().expect("save_data");
+  data.upgrades = upgrades;
+  data.write();
+}
@@ -1,7 +1,14 @@
 use crate::wasm4::*;
 use crate::utils::*;
 use crate::dragon::*;
+use crate::boss::*;
+use crate::entity::*;
 use crate::stage::*;
+use crate::assets::img::*;
+use crate::cutscene::*;
+use crate::lang::*;
+use crate::save::*;
+use crate::upgrade::*;
 
 // シーン管理用
 pub struct SceneHandler {
@@ -45,7 +52,7 @@ impl SceneData {
     match id {
       SceneId::Title => SceneData::Title(SceneTitle::new()),
       SceneId::Main => SceneData::Main(SceneMain::new()),
-      SceneId::EndingFalse => SceneData::FalseEnding(SceneFalseEnding::new()),
+      SceneId::EndingFalse(frag_count) => SceneData::FalseEnding(SceneFalseEnding::new(frag_count)),
       SceneId::EndingTrue => SceneData::TrueEnding(SceneTrueEnding::new()),
     }
   }
@@ -87,7 +94,7 @@ impl SceneData {
 pub enum SceneId {
   Title,
   Main,
-  EndingFalse,
+  EndingFalse(u8), // 集めた欠片の数
   EndingTrue,
 }
 
@@ -116,14 +123,19 @@ impl SceneTitle {
   }
 }
 impl SceneBehavior for SceneTitle {
-  fn start(&mut self) {}
+  fn start(&mut self) {
+    // エンディング等で変更されたパレットを戻す
+    set_palette(&DEFAULT_PALETTE);
+  }
   fn update(&mut self) {
     if is_just_pressed(BTN_Z) { self.is_start = true; }
+    // 左右で言語を切り替える(変更はすぐに保存)
+    if is_just_pressed(BTN_LEFT) || is_just_pressed(BTN_RIGHT) { set_lang(get_lang().next()); }
   }
   fn draw(&mut self) {
-    set_drawcolor_idx(0x04);
-    text("blood dragon", 28, 60);
-    text(b"press \x81 to start", 15, 120);
+    FONT_MAIN.draw_center_x(tr(StrId::TitleName), 60);
+    FONT_MAIN.draw_center_x(tr(StrId::TitlePressStart), 120);
+    FONT_MAIN.draw_center_x(&format!("< {} >", tr(StrId::LangName)), 140);
   }
   fn get_scene_cmd(&self) -> SceneCmd {
     if self.is_start {
@@ -137,9 +149,14 @@ impl SceneBehavior for SceneTitle {
 // メイン
 // -------------------------------
 const DISP_STAGE_NAME_FRAME: u16 = 90;
+const HUD_HEART_W: i32 = 8;
 pub struct SceneMain {
   dragon: Dragon,
+  boss: Boss,
   stage: StageHandler,
+  cutscene: CutscenePlayer,
+  upgrade_menu: UpgradeMenu,
+  upgrades: UpgradeLedger,
   total_frag_count: u8,
   disp_stage_name_clock: Clock,
   dragon_death_clock: Clock,
@@ -148,12 +165,17 @@ pub struct SceneMain {
   is_clear_stage: bool,
   is_clear_all_stage: bool,
   is_fix_cam: bool,
+  is_boss_cleared: bool,
 }
 impl SceneMain {
   pub fn new() -> Self {
     Self {
       dragon: Dragon::new(),
+      boss: Boss::new(),
       stage: StageHandler::new(),
+      cutscene: CutscenePlayer::new(),
+      upgrade_menu: UpgradeMenu::new(),
+      upgrades: get_upgrades(),
       total_frag_count: 0,
       disp_stage_name_clock: Clock::new(DISP_STAGE_NAME_FRAME),
       dragon_death_clock: Clock::new(90),
@@ -162,16 +184,60 @@ impl SceneMain {
       is_clear_stage: false,
       is_clear_all_stage: false,
       is_fix_cam: false,
+      is_boss_cleared: false,
     }
 
   }
+  // ステージ開始時のカットシーンを再生
+  fn play_stage_intro(&mut self) {
+    let cam = self.get_cam();
+    self.cutscene.play(self.stage.get_intro(), cam);
+  }
+  // 竜を追うカメラ(固定されたステージでは固定座標)
+  fn get_cam(&self) -> Vec2i {
+    match self.stage.get_cam_lock() {
+      Some(cam) => cam,
+      None => Vec2i::new(-self.dragon.pos.x + CAM_OFFSET_X, -self.dragon.pos.y + CAM_OFFSET_Y),
+    }
+  }
+  fn is_boss_stage(&self) -> bool { self.stage.get_stage_id() == StageID::LastBattle }
+  // ばねの勢いでひび割れた壁を突き破る(衝突判定より先に壊す)
+  fn break_walls_by_spring(&mut self) {
+    let tiles = self.stage.get_tiles_from_pos(self.dragon.pos.x, self.dragon.pos.y, DRAGON_WIDTH, DRAGON_HEIGHT);
+    let cmds = self.dragon.check_break(tiles[0], tiles[1], tiles[2], tiles[3]);
+    self.stage.update(cmds);
+  }
 }
 impl SceneBehavior for SceneMain {
   fn start(&mut self) {
-    self.dragon.pos = self.stage.get_start_pos();
+    self.dragon.can_wall_jump = self.stage.can_wall_jump();
+    self.dragon.physics = self.stage.get_physics();
+    self.dragon.upgrades = self.upgrades;
+    self.dragon.reset(self.stage.get_start_pos());
+    self.play_stage_intro();
   }
   fn update(&mut self) {
 
+    // ステージの合間の強化メニュー
+    if self.upgrade_menu.is_open() {
+      self.upgrade_menu.update();
+      // 閉じたら保存して次のステージを始める
+      if !self.upgrade_menu.is_open() {
+        self.upgrades = self.upgrade_menu.ledger;
+        set_upgrades(self.upgrades);
+        self.dragon.upgrades = self.upgrades;
+        self.dragon.reset(self.stage.get_start_pos());
+        self.play_stage_intro();
+      }
+      return
+    }
+
+    // カットシーン再生中
+    if self.cutscene.is_playing() {
+      self.cutscene.update();
+      return
+    }
+
     // ステージ開始時のみステージ名を描画
     if self.is_start_stage {
       self.disp_stage_name_clock.tick();
@@ -190,7 +256,9 @@ impl SceneBehavior for SceneMain {
 
         self.dragon_death_clock.reset();
         self.stage.reload();
-        self.dragon.reset(self.stage.get_start_pos());
+        self.dragon.reset(self.stage.get_respawn_pos());
+        self.boss.reset();
+        self.is_boss_cleared = false;
         self.is_fix_cam = false;
         self.is_start_stage = true;
       }
@@ -202,38 +270,69 @@ impl SceneBehavior for SceneMain {
       return
     }
 
+    // 移動床
+    self.stage.update_platforms();
+    self.dragon.carry(&self.stage.platforms);
     // 横軸の移動処理
     self.dragon.update_x();
+    self.break_walls_by_spring();
     let tiles = self.stage.get_tiles_from_pos(self.dragon.pos.x, self.dragon.pos.y, DRAGON_WIDTH, DRAGON_HEIGHT);
-    self.dragon.check_collision_x(tiles[0], tiles[1], tiles[2], tiles[3]);
+    self.dragon.check_collision_x(tiles[0], tiles[1], tiles[2], tiles[3], &self.stage.platforms);
     // 縦軸の移動距離
     self.dragon.update_y();
+    self.break_walls_by_spring();
     let tiles = self.stage.get_tiles_from_pos(self.dragon.pos.x, self.dragon.pos.y, DRAGON_WIDTH, DRAGON_HEIGHT);
-    self.dragon.check_collision_y(tiles[0], tiles[1], tiles[2], tiles[3]);
+    self.dragon.check_collision_y(tiles[0], tiles[1], tiles[2], tiles[3], &self.stage.platforms);
     // アイテム等、インタラクティブな物への衝突判定処理
     let tiles = self.stage.get_tiles_from_pos(self.dragon.pos.x, self.dragon.pos.y, DRAGON_WIDTH, DRAGON_HEIGHT);
     let interactive_cmds = self.dragon.check_interactive(tiles[0], tiles[1], tiles[2], tiles[3]);
+    self.stage.update_crumbles(self.dragon.pos, self.dragon.get_feet_y());
     
     // ステージクリア時処理
     if interactive_cmds.contains(&Some(InteractiveCmd::ClearStage)) {
       self.is_clear_stage = true;
     }
 
-    // debug
-    if is_just_pressed(BTN_UP) {
-      self.is_clear_stage = true;
-    }
-
     // 更新処理
     self.stage.update(interactive_cmds);
+    // ブレス攻撃
+    if let Some((pos, vel)) = self.dragon.update_breath() {
+      self.stage.entities.spawn(EntityKind::Breath, pos, vel);
+    }
+    self.stage.update_entities(self.dragon.pos);
+    // ブレスが壁に当たったら消える音
+    if self.stage.entities.get_hits().any(|h| h.shooter == EntityKind::Breath && matches!(h.target, HitTarget::Tile(..))) {
+      tone(200 | (100 << 16), 4, 30, TONE_NOISE);
+    }
+    // 竜とエンティティの衝突
+    let (x, y, w, h) = self.dragon.get_hurtbox();
+    for e in self.stage.entities.query_mut(x, y, w, h) {
+      e.collide_dragon(&mut self.dragon);
+    }
+    if self.is_boss_stage() {
+      self.boss.update(&mut self.dragon, &mut self.stage.entities);
+      // 撃破したらゴールが開く
+      if self.boss.is_defeated() && !self.is_boss_cleared {
+        self.is_boss_cleared = true;
+        self.stage.open_goal();
+        self.cutscene.play(SCRIPT_LAST_BATTLE_VICTORY, self.get_cam());
+      }
+    }
     self.dragon.update();
 
     // ステージ移行処理
     if self.is_clear_stage {
       self.total_frag_count += self.stage.fragment_count;
+      // 集めた欠片は強化に使えるように貯めておく(前に貯めた欠片は数えない)
+      self.upgrades.deposit(self.stage.get_stage_idx(), self.stage.get_fragment_mask());
+      set_upgrades(self.upgrades);
       let exists_next_stage = self.stage.goto_next_stage();
       if exists_next_stage {
-        self.dragon.reset(self.stage.get_start_pos());
+        self.dragon.can_wall_jump = self.stage.can_wall_jump();
+        self.dragon.physics = self.stage.get_physics();
+        self.boss.reset();
+        // 竜のリセットとカットシーンはメニューを閉じた後
+        self.upgrade_menu.open(self.upgrades);
       }
       else { self.is_clear_all_stage = true; } // オールクリア
       self.is_start_stage = true;
@@ -244,11 +343,26 @@ impl SceneBehavior for SceneMain {
   }
 
   fn draw(&mut self) {
+    if self.upgrade_menu.is_open() {
+      self.upgrade_menu.draw();
+      return
+    }
+    // カットシーンのカメラ
+    if self.cutscene.is_playing() {
+      let cam = self.cutscene.get_cam();
+      self.stage.draw(cam.x, cam.y);
+      if self.is_boss_stage() { self.boss.draw(cam.x, cam.y); }
+      self.dragon.draw(cam.x, cam.y);
+      self.cutscene.draw();
+      return
+    }
     // 通常カメラ
     if !self.is_fix_cam {
-      let cam_x = -self.dragon.pos.x + 70;
-      let cam_y = -self.dragon.pos.y + 80;
+      let cam = self.get_cam();
+      let cam_x = cam.x;
+      let cam_y = cam.y;
       self.stage.draw(cam_x, cam_y);
+      if self.is_boss_stage() { self.boss.draw(cam_x, cam_y); }
       if !self.dragon.is_death { self.dragon.draw(cam_x, cam_y); } 
       else { self.dragon.draw_death(cam_x, cam_y); }
       // カメラ座標の保存
@@ -258,36 +372,53 @@ impl SceneBehavior for SceneMain {
     // 固定カメラ
     else {
       self.stage.draw(self.fix_cam_pos.x, self.fix_cam_pos.y);
+      if self.is_boss_stage() { self.boss.draw(self.fix_cam_pos.x, self.fix_cam_pos.y); }
       if !self.dragon.is_death { self.dragon.draw(self.fix_cam_pos.x, self.fix_cam_pos.y); }
       else { self.dragon.draw_death(self.fix_cam_pos.x, self.fix_cam_pos.y); }
     }
 
-    // HPゲージの描画
-    set_drawcolor(2, 3);
-    for i in 0..self.dragon.hp as i32 {
-      rect(5 + 6 * i, 145, 5, 10);
+    // HPの描画(減った分は空のハート)
+    for i in 0..self.dragon.get_max_hp() {
+      let img = if i < self.dragon.hp { &IMG_HUD_HEART_FULL } else { &IMG_HUD_HEART_EMPTY };
+      img.draw(4 + HUD_HEART_W * i as i32, 146);
+    }
+    // 飛行の体力ゲージ(HPの最大値の分だけ右に置く)
+    let gauge_x = 6 + HUD_HEART_W * self.dragon.get_max_hp() as i32;
+    let gauge_w = self.dragon.get_max_stamina() as u32 / 2 + 2;
+    set_drawcolor(1, 4);
+    rect(gauge_x, 147, gauge_w, 6);
+    set_drawcolor(3, 3);
+    if self.dragon.get_stamina() >= 2 { rect(gauge_x + 1, 148, self.dragon.get_stamina() as u32 / 2, 4); }
+    // 二段ジャンプ(使える間は濃く表示)
+    if self.upgrades.has(Upgrade::DoubleJump) {
+      let x = gauge_x + gauge_w as i32 + 6;
+      set_drawcolor_idx(if self.dragon.is_double_jump_ready() { 0x04 } else { 0x02 });
+      for i in 0..3 {
+        hline(x - i, 146 + i, 1 + 2 * i as u32);
+        hline(x - i, 150 + i, 1 + 2 * i as u32);
+      }
     }
 
+    // ボスのHPゲージ
+    if self.is_boss_stage() { self.boss.draw_hp_bar(); }
+
     // ステージ開始時のみステージ名を描画
     if self.is_start_stage {
-      set_drawcolor(2, 4);
-      let stage = String::from("stage ");
-      let stage_idx = &(self.stage.get_stage_idx() + 1).to_string();
-      text_center_x(stage + stage_idx, 50);
+      FONT_MAIN.draw_center_x(&tr_fmt(StrId::StageNum, &[self.stage.get_stage_idx() as u32 + 1]), 50);
+      FONT_MAIN.draw_center_x(self.stage.get_stage_name(), 64);
     }
 
     // 欠片の個数の描画
-    set_drawcolor(2, 4);
     let frag_count = self.stage.fragment_count + self.total_frag_count;
-    text(frag_count.to_string(), 150, 1);
+    FONT_HUD.draw(&tr_fmt(StrId::HudFragment, &[frag_count as u32]), 150, 1);
 
 
   }
   
   fn get_scene_cmd(&self) -> SceneCmd {
     if self.is_clear_all_stage {
-      if self.total_frag_count == 8 { SceneCmd::Change(SceneId::EndingTrue) }
-      else { SceneCmd::Change(SceneId::EndingFalse) }
+      if self.total_frag_count >= TOTAL_FRAGMENT_COUNT { SceneCmd::Change(SceneId::EndingTrue) }
+      else { SceneCmd::Change(SceneId::EndingFalse(self.total_frag_count)) }
     }
     else { SceneCmd::None }
   }
@@ -296,50 +427,192 @@ impl SceneBehavior for SceneMain {
 }
 
 
-pub struct SceneFalseEnding {
+// -------------------------------
+// エンディング共通
+// -------------------------------
+const ENDING_CREDIT_WAIT: u16 = 2;  // スタッフロールを1px送るまでのフレーム数
+const ENDING_CREDIT_LINE_H: i32 = 12;
+// 役職は言語ごとに訳し、名前はそのまま表示する
+enum Credit {
+  Role(StrId),
+  Name(&'static str),
+  Space,
+}
+const DAT_CREDITS: &[Credit] = &[
+  Credit::Role(StrId::CreditStaff),
+  Credit::Space,
+  Credit::Role(StrId::CreditProgram),
+  Credit::Name("gkanti"),
+  Credit::Space,
+  Credit::Role(StrId::CreditGraphics),
+  Credit::Name("gkanti"),
+  Credit::Space,
+  Credit::Role(StrId::CreditStageDesign),
+  Credit::Name("gkanti"),
+  Credit::Space,
+  Credit::Role(StrId::CreditThanks),
+  Credit::Name("wasm-4"),
+  Credit::Space,
+  Credit::Role(StrId::CreditAndYou),
+];
+
+#[derive(Copy, Clone, PartialEq)]
+enum EndingPhase {
+  Story,
+  Credits,
+  End,
+}
 
+// 物語(カットシーン) -> スタッフロール -> 締めの画面、の順に進行させる
+struct EndingSequence {
+  cutscene: CutscenePlayer,
+  phase: EndingPhase,
+  credit_frames: u16,
+  is_finish: bool,
+}
+impl EndingSequence {
+  fn new(story: &'static [CutsceneCmd]) -> Self {
+    let mut cutscene = CutscenePlayer::new();
+    cutscene.play(story, Vec2i::zero());
+    Self {
+      cutscene,
+      phase: EndingPhase::Story,
+      credit_frames: 0,
+      is_finish: false,
+    }
+  }
+  fn update(&mut self) {
+    match self.phase {
+      EndingPhase::Story => {
+        self.cutscene.update();
+        if !self.cutscene.is_playing() { self.phase = EndingPhase::Credits; }
+      }
+      EndingPhase::Credits => {
+        // Z長押しで早送り
+        self.credit_frames += if is_pressed(BTN_Z) { 4 } else { 1 };
+        if self.get_credit_top() + (DAT_CREDITS.len() as i32 * ENDING_CREDIT_LINE_H) < 0 {
+          self.phase = EndingPhase::End;
+        }
+      }
+      EndingPhase::End => {
+        if is_just_pressed(BTN_Z) { self.is_finish = true; }
+      }
+    }
+  }
+  fn get_credit_top(&self) -> i32 {
+    160 - (self.credit_frames / ENDING_CREDIT_WAIT) as i32
+  }
+  fn draw(&self) {
+    match self.phase {
+      EndingPhase::Story => {
+        self.cutscene.draw();
+      }
+      EndingPhase::Credits => {
+        let top = self.get_credit_top();
+        for (i, line) in DAT_CREDITS.iter().enumerate() {
+          let y = top + i as i32 * ENDING_CREDIT_LINE_H;
+          // 画面外の行は描画しない
+          if !(-8..=160).contains(&y) { continue; }
+          match line {
+            Credit::Role(id) => FONT_MAIN.draw_center_x(tr(*id), y),
+            Credit::Name(name) => FONT_MAIN.draw_center_x(name, y),
+            Credit::Space => {}
+          }
+        }
+      }
+      EndingPhase::End => {
+        FONT_MAIN.draw_center_x(tr(StrId::EndThanks), 24);
+        FONT_MAIN.draw_center_x(tr(StrId::EndPressTitle), 130);
+      }
+    }
+  }
+}
+
+// -------------------------------
+// エンディング(欠片が足りない)
+// -------------------------------
+pub struct SceneFalseEnding {
+  ending: EndingSequence,
+  frag_count: u8,
 }
 
 impl SceneFalseEnding {
-  pub fn new() -> Self {
-    Self {  }
+  pub fn new(frag_count: u8) -> Self {
+    Self {
+      ending: EndingSequence::new(SCRIPT_ENDING_FALSE),
+      frag_count,
+    }
   }
 }
 impl SceneBehavior for SceneFalseEnding {
-  fn start(&mut self) {
-      
-  }
+  fn start(&mut self) {}
   fn update(&mut self) {
-      
+    self.ending.update();
   }
   fn draw(&mut self) {
-      
+    if self.ending.phase == EndingPhase::Story {
+      // 地面
+      set_drawcolor_idx(0x03);
+      hline(0, 86, 160);
+    }
+    if self.ending.phase == EndingPhase::End {
+      // 集めた欠片の数を表示
+      IMG_ITEM_FRAGMENT.draw(52, 92);
+      FONT_MAIN.draw(&tr_fmt(StrId::EndFragment, &[self.frag_count as u32, TOTAL_FRAGMENT_COUNT as u32]), 72, 96);
+    }
+    self.ending.draw();
   }
   fn get_scene_cmd(&self) -> SceneCmd {
-    return SceneCmd::None;
+    if self.ending.is_finish { SceneCmd::Change(SceneId::Title) }
+    else { SceneCmd::None }
   }
 }
 
+// -------------------------------
+// エンディング(欠片を全て集めた)
+// -------------------------------
+const TRUE_ENDING_ORBIT_R: f32 = 40.0;
+const TRUE_ENDING_ORBIT_FRAME: u16 = 360; // 欠片が竜に集まりきるまでの時間
 pub struct SceneTrueEnding {
-
+  ending: EndingSequence,
+  anim: Timeline,
+  frames: u16,
 }
 
 impl SceneTrueEnding {
   pub fn new() -> Self {
-    Self {  }
+    Self {
+      ending: EndingSequence::new(SCRIPT_ENDING_TRUE),
+      anim: Timeline::new(ANIM_DRAGON_FLY, [4, 4].to_vec()),
+      frames: 0,
+    }
   }
 }
 impl SceneBehavior for SceneTrueEnding {
-  fn start(&mut self) {
-      
-  }
+  fn start(&mut self) {}
   fn update(&mut self) {
-      
+    self.ending.update();
+    self.anim.play();
+    self.frames = self.frames.wrapping_add(1);
   }
   fn draw(&mut self) {
-      
+    // 竜の周りを欠片が回りながら集まってくる
+    if self.ending.phase == EndingPhase::Story && self.frames < TRUE_ENDING_ORBIT_FRAME {
+      let r = TRUE_ENDING_ORBIT_R * (1.0 - self.frames as f32 / TRUE_ENDING_ORBIT_FRAME as f32);
+      for i in 0..TOTAL_FRAGMENT_COUNT {
+        let rad = (self.frames as f32 / 30.0) + (i as f32 * std::f32::consts::TAU / TOTAL_FRAGMENT_COUNT as f32);
+        let x = 72 + (rad.cos() * r) as i32;
+        let y = 56 + (rad.sin() * r) as i32;
+        IMG_ITEM_FRAGMENT.draw(x, y);
+      }
+    }
+    if self.ending.phase == EndingPhase::End {
+      self.anim.draw(72, 56 + ((self.frames / 16) % 2) as i32);
+    }
+    self.ending.draw();
   }
   fn get_scene_cmd(&self) -> SceneCmd {
-    return SceneCmd::None;
+    if self.ending.is_finish { SceneCmd::Change(SceneId::Title) }
+    else { SceneCmd::None }
   }
-}
\ No newline at end of file
+}
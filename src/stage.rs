@@ -1,6 +1,12 @@
 use crate::wasm4::*;
-use crate::assets::img::{TILE_STAGE};
+use crate::assets::img::{TILE_STAGE, ANIM_TILE_CRUMBLE, IMG_DRAGON_IDLE_01, ANIM_DRAGON_IDLE, ANIM_DRAGON_WALK, ANIM_DRAGON_FLY};
 use crate::utils::*;
+use crate::cutscene::*;
+use crate::entity::*;
+use crate::platform::*;
+use crate::physics::*;
+use crate::lang::*;
+use crate::dragon::{DRAGON_WIDTH, DRAGON_HEIGHT};
 
 // -------------------------------
 // Enums
@@ -32,18 +38,130 @@ pub enum TileId {
 
   Key,
   Fragment,
+  CrackedWall, // 攻撃や強いばねの勢いで壊れる壁
+  OneWay,      // 下からすり抜けられ、上に乗れる床
+  Crumble,     // 乗ると崩れ、しばらくすると元に戻る床
+  ConveyorLeft,
+  ConveyorRight,
+  Ice,
+  GravityUp,   // 触れると重力が上向きになる
+  GravityDown, // 触れると重力が下向きに戻る
+  Stamina,     // 触れると飛行の体力が全快する
+  Checkpoint,  // 触れると復活地点になる
+  CheckpointActive,
+  Heart,       // 触れるとHPが回復する(HPが満タンなら取れない)
+}
+impl TileId {
+  // 通り抜けられないタイル
+  pub fn is_solid(self) -> bool {
+    matches!(self, TileId::Wall | TileId::CrackedWall | TileId::Crumble | TileId::SpringVert | TileId::SpringHori | TileId::NeedleDown | TileId::NeedleLeft | TileId::NeedleRight | TileId::NeedleUp
+      | TileId::ConveyorLeft | TileId::ConveyorRight | TileId::Ice)
+  }
+  // ダメージを受ける範囲(タイルの左上からの相対位置)
+  // 針は先端側の半分だけ。固いタイルなので、触れただけで届くように面から少しはみ出させる
+  pub fn get_damage_box(self) -> Option<Hitbox> {
+    let (half, reach) = (STAGE_TILE_SIZE as i16 / 2, NEEDLE_REACH);
+    match self {
+      TileId::NeedleUp    => Some(Hitbox::new(half / 2, -reach, half, half + reach)),
+      TileId::NeedleDown  => Some(Hitbox::new(half / 2, half, half, half + reach)),
+      TileId::NeedleRight => Some(Hitbox::new(half, half / 2, half + reach, half)),
+      TileId::NeedleLeft  => Some(Hitbox::new(-reach, half / 2, half + reach, half)),
+      _ => None,
+    }
+  }
+  // 触れたときの重力の向き(trueなら上向き)
+  pub fn get_gravity_flip(self) -> Option<bool> {
+    match self {
+      TileId::GravityUp   => Some(true),
+      TileId::GravityDown => Some(false),
+      _ => None,
+    }
+  }
+  // 上に立ったときの床の性質
+  pub fn get_surface(self) -> Surface {
+    match self {
+      TileId::ConveyorLeft  => Surface::Conveyor(-CONVEYOR_PUSH),
+      TileId::ConveyorRight => Surface::Conveyor(CONVEYOR_PUSH),
+      TileId::Ice           => Surface::Ice,
+      _ => Surface::Normal,
+    }
+  }
+}
+// 床の性質
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Surface {
+  Normal,
+  Conveyor(i16), // 横方向に押し流す
+  Ice,           // 止まりにくい
 }
 // プレイヤーの行動でステージに変化がある際のコマンド集
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub enum InteractiveCmd {
   GetKey(u8, u8),
   GetFragment(u8, u8),
+  GetStamina(u8, u8),
+  TouchCheckpoint(u8, u8),
+  GetHeart(u8, u8),
+  BreakWall(u8, u8),
   ClearStage,
 }
 
+// 崩れる床の状態
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum CrumbleState {
+  Idle,
+  Shake(u16), // 乗られてからのフレーム数
+  Gone(u16),  // 消えてからのフレーム数
+}
+impl CrumbleState {
+  // 次のフレームの状態(is_blockedは竜と重なっていて戻せないとき)
+  fn next(self, is_stood: bool, is_blocked: bool) -> Self {
+    match self {
+      CrumbleState::Idle if is_stood => CrumbleState::Shake(0),
+      CrumbleState::Idle => CrumbleState::Idle,
+      CrumbleState::Shake(f) if f >= CRUMBLE_SHAKE_TIME => CrumbleState::Gone(0),
+      CrumbleState::Shake(f) => CrumbleState::Shake(f + 1),
+      CrumbleState::Gone(f) if f >= CRUMBLE_RESPAWN_TIME => if is_blocked { CrumbleState::Gone(f) } else { CrumbleState::Idle },
+      CrumbleState::Gone(f) => CrumbleState::Gone(f + 1),
+    }
+  }
+}
+
 // -------------------------------
 // Structs
 // -------------------------------
+// 崩れる床ひとつ分のタイマー
+#[derive(Copy, Clone, Debug)]
+struct Crumble {
+  idx: usize,
+  state: CrumbleState,
+}
+// 触れたチェックポイントと、その時点で取っていたもの
+#[derive(Clone, Debug)]
+struct Checkpoint {
+  idx: usize,
+  collected: Vec<usize>, // 取った鍵・欠片のインデックス
+  goal_opened: bool,
+}
+// チェックポイントから復活したときに残すもの
+// 鍵とゴールは必ず一緒に戻す(鍵だけ残すと詰み、ゴールだけ残すと鍵を取り直せる)
+#[derive(Copy, Clone, Debug)]
+struct CheckpointKeep {
+  keys: bool,      // 取った鍵と開いたゴール
+  fragments: bool, // 取った欠片
+}
+const CHECKPOINT_KEEP_ALL: CheckpointKeep = CheckpointKeep { keys: true, fragments: true };
+impl CheckpointKeep {
+  // 取ったまま残すタイルか
+  fn keeps(self, id: TileId) -> bool {
+    match id {
+      TileId::Key => self.keys,
+      TileId::Fragment => self.fragments,
+      _ => false,
+    }
+  }
+  fn keeps_goal_opened(self, goal_opened: bool) -> bool { goal_opened && self.keys }
+}
 // 不変の要素
 #[derive(Copy, Clone, Debug)]
 pub struct Tile {
@@ -63,9 +181,19 @@ impl Tile {
 
 // ステージのデータ格納用
 struct StageData {
+  id: StageID,
   width: u8,
   height: u8,
   data: &'static [u8],
+  name: StrId,                   // ステージ名(開始時に表示)
+  intro: &'static [CutsceneCmd], // ステージ開始時のカットシーン(空なら無し)
+  cam_lock: Option<Vec2i>,       // カメラを固定する座標(Noneなら竜を追う)
+  keep_broken: bool,             // 壊した壁を死亡後も残す(falseなら死亡で元に戻る)
+  checkpoint_keep: CheckpointKeep,
+  respawn_hearts: bool,          // 取ったハートを死亡後に戻す
+  platforms: &'static [PlatformDef],
+  wall_jump: bool,               // 壁ずり・壁ジャンプを使えるか
+  physics: &'static PhysicsProfile, // 竜の挙動パラメータ
 }
 impl StageData {
   // 文字のステージデータをTileIDへ変換する
@@ -96,6 +224,17 @@ impl StageData {
       b'k' => { TileId::Key }
       b'f' => { TileId::Fragment }
       b'g' => { TileId::GoalClosed }
+      b'x' => { TileId::CrackedWall }
+      b'-' => { TileId::OneWay }
+      b'c' => { TileId::Crumble }
+      b'{' => { TileId::ConveyorLeft }
+      b'}' => { TileId::ConveyorRight }
+      b'i' => { TileId::Ice }
+      b'u' => { TileId::GravityUp }
+      b'd' => { TileId::GravityDown }
+      b'o' => { TileId::Stamina }
+      b'p' => { TileId::Checkpoint }
+      b'h' => { TileId::Heart }
       _    => { TileId::Empty }
     }
 
@@ -109,6 +248,14 @@ pub struct StageHandler {
   start_tile_idx: usize,
   goal_tile_idx:  usize,
   pub fragment_count: u8,
+  pub entities: EntityPool,
+  pub platforms: Vec<MovingPlatform>,
+  broken_walls: Vec<usize>, // このステージで壊した壁のインデックス
+  used_hearts: Vec<usize>,  // このステージで取ったハートのインデックス
+  particles: [Particle; MAX_PARTICLE_VALUE],
+  crumbles: Vec<Crumble>,
+  checkpoint: Option<Checkpoint>, // 最後に触れたチェックポイント
+  collected: Vec<usize>,          // このステージで取った鍵・欠片のインデックス
   tiles: Vec<Tile>
 }
 impl StageHandler {
@@ -119,6 +266,14 @@ impl StageHandler {
       start_tile_idx: 0,
       goal_tile_idx: 0,
       fragment_count: 0,
+      entities: EntityPool::new(),
+      platforms: Vec::new(),
+      broken_walls: Vec::new(),
+      used_hearts: Vec::new(),
+      particles: [Particle::new(); MAX_PARTICLE_VALUE],
+      crumbles: Vec::new(),
+      checkpoint: None,
+      collected: Vec::new(),
       tiles: Vec::with_capacity(MAX_STAGE_DATA_SIZE)
     };
     result.setup();
@@ -126,9 +281,130 @@ impl StageHandler {
   }
   fn setup(&mut self) {
     self.now_stage.get_tiles(&mut self.tiles);
+    // 壊した壁を残すステージなら、再挑戦時も壊れたままにする
+    if self.now_stage.keep_broken {
+      for idx in self.broken_walls.iter() { self.tiles[*idx].id = TileId::Empty; }
+    } else { self.broken_walls.clear(); }
+    // ハートが戻らないステージなら、取ったものは消したままにする
+    if !self.now_stage.respawn_hearts {
+      for idx in self.used_hearts.iter() { self.tiles[*idx].id = TileId::Empty; }
+    } else { self.used_hearts.clear(); }
+    self.particles = [Particle::new(); MAX_PARTICLE_VALUE];
+    self.crumbles = self.tiles.iter().enumerate()
+      .filter(|(_, t)| t.id == TileId::Crumble)
+      .map(|(idx, _)| Crumble { idx, state: CrumbleState::Idle })
+      .collect();
     self.fragment_count = 0;
     self.start_tile_idx = self.tiles.iter().position(|t| t.id == TileId::Start).unwrap();
     self.goal_tile_idx = self.tiles.iter().position(|t| t.id == TileId::GoalClosed).unwrap();
+    self.collected.clear();
+    self.restore_checkpoint();
+    self.spawn_entities();
+    self.platforms = self.now_stage.platforms.iter().map(MovingPlatform::new).collect();
+  }
+  // チェックポイントに触れていたら、その時点で取っていたものを設定に従って戻す
+  fn restore_checkpoint(&mut self) {
+    let Some(cp) = self.checkpoint.clone() else { return };
+    let keep = self.now_stage.checkpoint_keep;
+    self.tiles[cp.idx].id = TileId::CheckpointActive;
+    for idx in cp.collected {
+      let id = self.tiles[idx].id;
+      if !keep.keeps(id) { continue }
+      if id == TileId::Fragment { self.fragment_count += 1; }
+      self.tiles[idx].id = TileId::Empty;
+      self.collected.push(idx);
+    }
+    if keep.keeps_goal_opened(cp.goal_opened) { self.tiles[self.goal_tile_idx].id = TileId::GoalOpened; }
+  }
+  fn touch_checkpoint(&mut self, idx: usize) {
+    if self.tiles[idx].id != TileId::Checkpoint { return }
+    if let Some(cp) = &self.checkpoint { self.tiles[cp.idx].id = TileId::Checkpoint; }
+    self.tiles[idx].id = TileId::CheckpointActive;
+    self.checkpoint = Some(Checkpoint {
+      idx,
+      collected: self.collected.clone(),
+      goal_opened: self.tiles[self.goal_tile_idx].id == TileId::GoalOpened,
+    });
+    tone(440 | (880 << 16), 16, 50, TONE_PULSE1);
+  }
+  // ステージデータの文字からエンティティを出現させる
+  fn spawn_entities(&mut self) {
+    self.entities.clear();
+    let width = self.now_stage.width as usize;
+    for (i, c) in self.now_stage.data.iter().enumerate() {
+      if let Some(kind) = ascii_to_entity_kind(*c) {
+        let pos = Vec2i::new((i % width) as i16 * STAGE_TILE_SIZE as i16, (i / width) as i16 * STAGE_TILE_SIZE as i16);
+        self.entities.spawn(kind, pos, kind.get_spawn_vel());
+      }
+    }
+  }
+  // 崩れる床(竜が乗ったら揺れ始め、消えた後に竜がいなければ元に戻る)
+  // feet_yは竜の足元のy座標(空中ならNone)
+  pub fn update_crumbles(&mut self, dragon_pos: Vec2i, feet_y: Option<i16>) {
+    let get_feet_idx = |x: i16| feet_y.and_then(|y| self.get_tile_from_pos(x, y)).map(|t| self.get_idx_from_tile_pos(t.local_x, t.local_y));
+    let feet = [get_feet_idx(dragon_pos.x), get_feet_idx(dragon_pos.x + DRAGON_WIDTH - 1)];
+    for i in 0..self.crumbles.len() {
+      let Crumble { idx, state } = self.crumbles[i];
+      // 竜と重なっていたら戻さない
+      let tile = self.tiles[idx];
+      let size = STAGE_TILE_SIZE as i16;
+      let tile_rect = (tile.get_global_pos_x(), tile.get_global_pos_y(), size, size);
+      let is_blocked = is_overlap(tile_rect, (dragon_pos.x, dragon_pos.y, DRAGON_WIDTH, DRAGON_HEIGHT));
+      let next = state.next(feet.contains(&Some(idx)), is_blocked);
+      match (state, next) {
+        (CrumbleState::Idle, CrumbleState::Shake(_)) => { tone(120 | (100 << 16), 10, 30, TONE_NOISE); }
+        (CrumbleState::Shake(_), CrumbleState::Gone(_)) => { self.tiles[idx].id = TileId::Empty; }
+        (CrumbleState::Gone(_), CrumbleState::Idle) => { self.tiles[idx].id = TileId::Crumble; }
+        _ => {}
+      }
+      self.crumbles[i].state = next;
+    }
+  }
+  pub fn update_platforms(&mut self) {
+    for p in self.platforms.iter_mut() { p.update(); }
+  }
+  pub fn update_entities(&mut self, dragon_pos: Vec2i) {
+    self.entities.clear_hits();
+    for i in 0..MAX_ENTITY {
+      let mut e = self.entities.get(i);
+      if !e.is_active() { continue }
+      let shooter = e.kind;
+      let event = e.update(self, dragon_pos);
+      self.entities.set(i, e);
+      match event {
+        Some(EntityEvent::Spawn(kind, pos, vel)) => { self.entities.spawn(kind, pos, vel); }
+        Some(EntityEvent::HitTile(pos)) => {
+          let target = HitTarget::Tile((pos.x / STAGE_TILE_SIZE as i16) as u8, (pos.y / STAGE_TILE_SIZE as i16) as u8);
+          self.entities.push_hit(HitEvent { shooter, target });
+        }
+        None => {}
+      }
+    }
+    self.entities.resolve_attacks();
+    // ブレスが当たったひび割れた壁を壊す
+    let hit_tiles: Vec<(u8, u8)> = self.entities.get_hits()
+      .filter(|h| h.shooter == EntityKind::Breath)
+      .filter_map(|h| match h.target { HitTarget::Tile(x, y) => Some((x, y)), _ => None })
+      .collect();
+    for (x, y) in hit_tiles { self.break_wall(x, y); }
+    for p in self.particles.iter_mut().filter(|p| p.alive) { p.update(); }
+  }
+  // ひび割れた壁を空にして、崩れる演出を出す
+  fn break_wall(&mut self, x: u8, y: u8) {
+    let idx = self.get_idx_from_tile_pos(x, y);
+    if self.tiles[idx].id != TileId::CrackedWall { return }
+    self.tiles[idx].id = TileId::Empty;
+    self.broken_walls.push(idx);
+
+    let pos = Vec2i::new(x as i16 * STAGE_TILE_SIZE as i16, y as i16 * STAGE_TILE_SIZE as i16);
+    self.entities.spawn(EntityKind::Rubble, pos, Vec2i::zero());
+    // 破片を飛び散らせる
+    let center = Vec2i::new(pos.x + STAGE_TILE_SIZE as i16 / 2, pos.y + STAGE_TILE_SIZE as i16 / 2);
+    let mut free = self.particles.iter_mut().filter(|p| !p.alive);
+    for vec in DEBRIS_VEC.iter() {
+      if let Some(p) = free.next() { p.start(center, *vec, DEBRIS_LIFE); }
+    }
+    tone(150 | (40 << 16), 18, 80, TONE_NOISE);
   }
 
   fn get_idx_from_tile_pos(&self, x: u8, y: u8) -> usize {
@@ -151,14 +427,29 @@ impl StageHandler {
             if tile.id != TileId::Empty {
               self.tiles[idx].id = TileId::Empty;
               self.tiles[self.goal_tile_idx].id = TileId::GoalOpened;
+              self.collected.push(idx);
             }
           }
+          InteractiveCmd::BreakWall(x, y) => { self.break_wall(x, y); }
+          InteractiveCmd::TouchCheckpoint(x, y) => { self.touch_checkpoint(self.get_idx_from_tile_pos(x, y)); }
+          InteractiveCmd::GetHeart(x, y) => {
+            let idx = self.get_idx_from_tile_pos(x, y);
+            if self.tiles[idx].id == TileId::Heart {
+              self.tiles[idx].id = TileId::Empty;
+              self.used_hearts.push(idx);
+            }
+          }
+          InteractiveCmd::GetStamina(x, y) => {
+            let idx = self.get_idx_from_tile_pos(x, y);
+            self.tiles[idx].id = TileId::Empty;
+          }
           InteractiveCmd::GetFragment(x, y) => {
             let idx = self.get_idx_from_tile_pos(x, y);
             let tile = &mut self.tiles[idx];
             if tile.id != TileId::Empty {
               tile.id = TileId::Empty;
               self.fragment_count += 1;
+              self.collected.push(idx);
             }
 
            }
@@ -178,6 +469,9 @@ impl StageHandler {
     // ステージ切り替え処理
     else {
       self.now_stage = DAT_STAGE_ALL[self.now_stage_idx];
+      self.broken_walls.clear();
+      self.used_hearts.clear();
+      self.checkpoint = None;
       self.setup();
       return true;
     }
@@ -185,11 +479,21 @@ impl StageHandler {
   pub fn reload(&mut self) {
     self.setup();
   }
+  // 鍵を取らずにゴールを開ける(ボス撃破時など)
+  pub fn open_goal(&mut self) {
+    self.tiles[self.goal_tile_idx].id = TileId::GoalOpened;
+  }
 
   pub fn get_start_pos(&self) -> Vec2i {
     let start_tile = self.tiles[self.start_tile_idx];
     return Vec2i::new(start_tile.local_x as i16 * STAGE_TILE_SIZE as i16, start_tile.local_y as i16 * STAGE_TILE_SIZE as i16)
   }
+  // 死亡後に復活する座標(チェックポイントに触れていればそこ)
+  pub fn get_respawn_pos(&self) -> Vec2i {
+    let Some(cp) = &self.checkpoint else { return self.get_start_pos() };
+    let tile = self.tiles[cp.idx];
+    Vec2i::new(tile.local_x as i16 * STAGE_TILE_SIZE as i16, tile.local_y as i16 * STAGE_TILE_SIZE as i16)
+  }
   // 受け取った座標に存在するタイルを返す
   pub fn get_tile_from_pos(&self, px: i16, py: i16) -> Option<&Tile> {
     let idx = self.get_idx_from_pos(px, py);
@@ -197,6 +501,10 @@ impl StageHandler {
     if idx >= self.tiles.len() { return None; }
     Some(&self.tiles[idx])
   }
+  pub fn is_solid_at(&self, px: i16, py: i16) -> bool {
+    if px < 0 || py < 0 { return false }
+    self.get_tile_from_pos(px, py).is_some_and(|t| t.id.is_solid())
+  }
   pub fn get_tiles_from_pos(&self, x: i16, y: i16, w: i16, h: i16) -> [Option<&Tile>; 4] {
     [
       self.get_tile_from_pos(x, y),
@@ -205,7 +513,23 @@ impl StageHandler {
       self.get_tile_from_pos(x+w-1, y+h-1),
     ]
   }
+  fn get_crumble_state(&self, idx: usize) -> Option<CrumbleState> {
+    self.crumbles.iter().find(|c| c.idx == idx).map(|c| c.state)
+  }
   pub fn get_stage_idx(&self) -> usize { return self.now_stage_idx }
+  // 取った欠片を、ステージ内で何番目の欠片かのビットで返す(1ステージ8個まで)
+  pub fn get_fragment_mask(&self) -> u8 {
+    let data = self.now_stage.data;
+    self.collected.iter().filter(|idx| data[**idx] == b'f').fold(0, |mask, idx| {
+      mask | 1 << data[..*idx].iter().filter(|c| **c == b'f').count()
+    })
+  }
+  pub fn get_stage_id(&self) -> StageID { self.now_stage.id }
+  pub fn can_wall_jump(&self) -> bool { self.now_stage.wall_jump }
+  pub fn get_physics(&self) -> PhysicsProfile { *self.now_stage.physics }
+  pub fn get_cam_lock(&self) -> Option<Vec2i> { self.now_stage.cam_lock }
+  pub fn get_stage_name(&self) -> &'static str { tr(self.now_stage.name) }
+  pub fn get_intro(&self) -> &'static [CutsceneCmd] { self.now_stage.intro }
   pub fn draw(&self, offset_x: i16, offset_y: i16) {
     // ステージを描画する
     for y in 0..self.now_stage.height {
@@ -225,9 +549,22 @@ impl StageHandler {
            (draw_pos_y + STAGE_TILE_SIZE as i16) < 0 || draw_pos_y > 160
            { continue; }
         
+        // 揺れている崩れる床は震わせる
+        if let Some(CrumbleState::Shake(f)) = self.get_crumble_state(idx) {
+          let shake_x = draw_pos_x + [-1, 1][(f / 2 % 2) as usize];
+          ANIM_TILE_CRUMBLE[(f / 6 % 2) as usize].draw(shake_x as i32, draw_pos_y as i32);
+          continue;
+        }
         TILE_STAGE[tile.id as usize].draw(draw_pos_x as i32, draw_pos_y as i32);
       }
     }
+    for p in self.platforms.iter() { p.draw(offset_x, offset_y); }
+    self.entities.draw(offset_x, offset_y);
+    // 破片
+    set_drawcolor(4, 4);
+    for p in self.particles.iter().filter(|p| p.alive) {
+      rect((p.pos.x + offset_x) as i32, (p.pos.y + offset_y) as i32, 2, 2);
+    }
   }
 
 }
@@ -239,15 +576,120 @@ impl StageHandler {
 // Stage Data
 // -------------------------------
  // タイルの大きさ
-const STAGE_TILE_SIZE: u8 = 16;
+pub const STAGE_TILE_SIZE: u8 = 16;
+// 竜を追うカメラのオフセット(竜の座標からの差分)
+pub const CAM_OFFSET_X: i16 = 70;
+pub const CAM_OFFSET_Y: i16 = 80;
+// タイル(x, y)に竜がいるときのカメラ座標
+pub const fn tile_cam_pos(x: u8, y: u8) -> Vec2i {
+  Vec2i::new(CAM_OFFSET_X - x as i16 * STAGE_TILE_SIZE as i16, CAM_OFFSET_Y - y as i16 * STAGE_TILE_SIZE as i16)
+}
 const MAX_STAGE_DATA_SIZE: usize = 1024;
+// ベルトコンベアが押す力
+const CONVEYOR_PUSH: i16 = 1;
+// 針の判定が面からはみ出す長さ(竜の喰らい判定が縮んでいる分と揃える)
+const NEEDLE_REACH: i16 = 3;
+// 崩れる床
+const CRUMBLE_SHAKE_TIME: u16 = 30;   // 乗られてから消えるまで
+const CRUMBLE_RESPAWN_TIME: u16 = 120; // 消えてから戻るまで
+// 壁が崩れたときの破片
+const DEBRIS_LIFE: u8 = 14;
+const DEBRIS_VEC: [Vec2i; 6] = [
+  Vec2i::new(-2, -3), Vec2i::new(-1, -4), Vec2i::new(1, -4),
+  Vec2i::new(2, -3),  Vec2i::new(-2, 1),  Vec2i::new(2, 1),
+];
 // 全てのステージデータを格納
-const DAT_STAGE_ALL: &[&'static StageData; 4] = &[
+pub const STAGE_COUNT: usize = 5;
+const DAT_STAGE_ALL: &[&'static StageData; STAGE_COUNT] = &[
   &DAT_STAGE_1,
   &DAT_STAGE_2,
   &DAT_STAGE_3,
-  &DAT_STAGE_4
+  &DAT_STAGE_4,
+  &DAT_STAGE_LAST_BATTLE,
+];
+// 全ステージに配置された欠片の総数(真エンディングの条件)
+pub const TOTAL_FRAGMENT_COUNT: u8 = count_all_fragments();
+const fn count_all_fragments() -> u8 {
+  let mut count = 0;
+  let mut i = 0;
+  while i < DAT_STAGE_ALL.len() {
+    let data = DAT_STAGE_ALL[i].data;
+    let mut j = 0;
+    while j < data.len() {
+      if data[j] == b'f' { count += 1; }
+      j += 1;
+    }
+    i += 1;
+  }
+  count
+}
+// -------------------------------
+// Cutscene Data
+// -------------------------------
+// ステージ1: 鍵と門の場所を見せてからスタート地点へ
+const SCRIPT_STAGE_1_INTRO: &[CutsceneCmd] = &[
+  CutsceneCmd::Pan(tile_cam_pos(2, 4), 0),
+  CutsceneCmd::Dialog(StrId::Stage1Intro, Some(&IMG_DRAGON_IDLE_01)),
+  CutsceneCmd::Pan(tile_cam_pos(1, 18), 90),
+];
+// ステージ3: 塔のてっぺんから見下ろす
+const SCRIPT_STAGE_3_INTRO: &[CutsceneCmd] = &[
+  CutsceneCmd::Pan(tile_cam_pos(1, 5), 0),
+  CutsceneCmd::Text(StrId::Stage3Intro),
+  CutsceneCmd::WaitButton,
+  CutsceneCmd::ClearText,
+  CutsceneCmd::Pan(tile_cam_pos(2, 82), 240),
+];
+// ラストバトル: 何かが降りてくる気配
+const SCRIPT_LAST_BATTLE_INTRO: &[CutsceneCmd] = &[
+  CutsceneCmd::Wait(30),
+  CutsceneCmd::Text(StrId::LastBattleIntro),
+  CutsceneCmd::WaitOrButton(120),
+  CutsceneCmd::ClearText,
+];
+// ボス撃破後(ゴールが開く)
+pub const SCRIPT_LAST_BATTLE_VICTORY: &[CutsceneCmd] = &[
+  CutsceneCmd::Sound(523 | (1047 << 16), 40, 80, TONE_PULSE1),
+  CutsceneCmd::Text(StrId::LastBattleVictory),
+  CutsceneCmd::WaitOrButton(180),
+  CutsceneCmd::ClearText,
+];
+
+// エンディング
+pub const PALETTE_BLOOD: [u32; 4] = [0xf8e0d8, 0xd06060, 0x782830, 0x200810];
+pub static SCRIPT_ENDING_FALSE: &[CutsceneCmd] = &[
+  CutsceneCmd::Sprite(0, ANIM_DRAGON_WALK, 6, Vec2i::new(-16, 70)),
+  CutsceneCmd::Move(0, Vec2i::new(72, 70), 90),
+  CutsceneCmd::Anim(0, ANIM_DRAGON_IDLE, 255),
+  CutsceneCmd::Text(StrId::EndingEscaped),
+  CutsceneCmd::WaitOrButton(180),
+  CutsceneCmd::Text(StrId::EndingFalse1),
+  CutsceneCmd::WaitOrButton(180),
+  CutsceneCmd::Flip(0, true), // 振り返る
+  CutsceneCmd::Text(StrId::EndingFalse2),
+  CutsceneCmd::WaitOrButton(180),
+  CutsceneCmd::Flip(0, false),
+  CutsceneCmd::Anim(0, ANIM_DRAGON_WALK, 6),
+  CutsceneCmd::Text(StrId::EndingFalse3),
+  CutsceneCmd::Move(0, Vec2i::new(160, 70), 90),
+  CutsceneCmd::Hide(0),
+  CutsceneCmd::WaitOrButton(90),
 ];
+pub static SCRIPT_ENDING_TRUE: &[CutsceneCmd] = &[
+  CutsceneCmd::Sprite(0, ANIM_DRAGON_FLY, 4, Vec2i::new(72, 56)),
+  CutsceneCmd::Text(StrId::EndingEscaped),
+  CutsceneCmd::WaitOrButton(180),
+  CutsceneCmd::Text(StrId::EndingTrue1),
+  CutsceneCmd::WaitOrButton(180),
+  CutsceneCmd::Palette(&PALETTE_BLOOD),
+  CutsceneCmd::Sound(262 | (523 << 16), 60, 80, TONE_TRIANGLE),
+  CutsceneCmd::Wait(30),
+  CutsceneCmd::Text(StrId::EndingTrue2),
+  CutsceneCmd::WaitOrButton(180),
+  CutsceneCmd::Text(StrId::EndingTrue3),
+  CutsceneCmd::WaitOrButton(180),
+];
+
 // 2 -> 2 !
 // 2 -> 3 !
 // 3 -> 2 !
@@ -280,9 +722,19 @@ const STAGE_1: [u8; STAGE_1_WIDTH as usize*STAGE_1_HEIGHT as usize] = [
   b'#',b'#',b'#',b'#',b'#',b'#',b'#',b'#',b'#',b'#',b'#',b'#',b'#',b'#',b'#',b'#',b'#',b'#',b'#',b'#',b'#',b'#',b'#',b'#',b'#',b'#',b'#',b'#',b'#',b'#',b'#',b'#',
 ];
 const DAT_STAGE_1: StageData = StageData {
+  id: StageID::Stage1,
   width: STAGE_1_WIDTH,
   height: STAGE_1_HEIGHT,
-  data: &STAGE_1
+  data: &STAGE_1,
+  name: StrId::StageName1,
+  intro: SCRIPT_STAGE_1_INTRO,
+  cam_lock: None,
+  keep_broken: true,
+  checkpoint_keep: CHECKPOINT_KEEP_ALL,
+  respawn_hearts: true,
+  platforms: &[],
+  wall_jump: false,
+  physics: &PHYSICS_DEFAULT,
 };
 
 // Stage 2
@@ -321,9 +773,19 @@ const STAGE_2: [u8; STAGE_2_WIDTH as usize*STAGE_2_HEIGHT as usize] = [
   b'#',b'#',b'#',b'#',b'#',b'#',b'#',b'#',b'#',b'#',b'#',b'#',b'#',b'#',b'#',b'#',b'#',b'#',b'#',b'#',
 ];
 const DAT_STAGE_2: StageData = StageData {
+  id: StageID::Stage2,
   width: STAGE_2_WIDTH,
   height: STAGE_2_HEIGHT,
-  data: &STAGE_2
+  data: &STAGE_2,
+  name: StrId::StageName2,
+  intro: &[],
+  cam_lock: None,
+  keep_broken: false,
+  checkpoint_keep: CHECKPOINT_KEEP_ALL,
+  respawn_hearts: true,
+  platforms: &[],
+  wall_jump: false,
+  physics: &PHYSICS_DEFAULT,
 };
 
 
@@ -356,7 +818,7 @@ const STAGE_3: [u8; STAGE_3_WIDTH as usize*STAGE_3_HEIGHT as usize] = [
   b'#',b'>',b' ',b' ',b'Z',b'Z',b' ',b' ',b' ',b' ',b'<',b'#',
   b'#',b'#',b'^',b'^',b'^',b'^',b' ',b' ',b' ',b' ',b'<',b'#',
   b'#',b'#',b'#',b'#',b'#',b'#',b' ',b' ',b' ',b' ',b'<',b'#',
-  b'#',b' ',b' ',b' ',b' ',b' ',b' ',b' ',b' ',b' ',b'<',b'#',
+  b'#',b' ',b' ',b' ',b' ',b' ',b' ',b'p',b' ',b' ',b'<',b'#',
   b'#',b' ',b' ',b' ',b' ',b' ',b' ',b' ',b' ',b' ',b'<',b'#',
   b'#',b' ',b' ',b' ',b' ',b' ',b' ',b' ',b' ',b' ',b'<',b'#',
   b'#',b' ',b' ',b' ',b' ',b' ',b' ',b' ',b' ',b' ',b'<',b'#',
@@ -376,7 +838,7 @@ const STAGE_3: [u8; STAGE_3_WIDTH as usize*STAGE_3_HEIGHT as usize] = [
   b'#',b' ',b' ',b' ',b'#',b'#',b'#',b'#',b'#',b'#',b'#',b'#',
   b'#',b' ',b' ',b' ',b' ',b' ',b' ',b' ',b' ',b' ',b'f',b'#',
   b'#',b' ',b' ',b' ',b' ',b' ',b' ',b' ',b' ',b' ',b' ',b'#',
-  b'#',b' ',b'Z',b' ',b' ',b' ',b' ',b' ',b' ',b' ',b' ',b'#',
+  b'#',b' ',b'Z',b' ',b'p',b' ',b' ',b' ',b' ',b' ',b' ',b'#',
   b'#',b'#',b'#',b'#',b'#',b' ',b' ',b' ',b' ',b' ',b' ',b'#',
   b'#',b'#',b'#',b'#',b'>',b' ',b' ',b' ',b' ',b' ',b' ',b'#',
   b'#',b'#',b'#',b'#',b'>',b' ',b' ',b' ',b' ',b' ',b' ',b'#',
@@ -416,9 +878,19 @@ const STAGE_3: [u8; STAGE_3_WIDTH as usize*STAGE_3_HEIGHT as usize] = [
   b'#',b'#',b'#',b'#',b'#',b'#',b'#',b'#',b'#',b'#',b'#',b'#',
 ];
 const DAT_STAGE_3: StageData = StageData {
+  id: StageID::Stage3,
   width: STAGE_3_WIDTH,
   height: STAGE_3_HEIGHT,
   data: &STAGE_3,
+  name: StrId::StageName3,
+  intro: SCRIPT_STAGE_3_INTRO,
+  cam_lock: None,
+  keep_broken: false,
+  checkpoint_keep: CHECKPOINT_KEEP_ALL,
+  respawn_hearts: false,
+  platforms: &[],
+  wall_jump: false,
+  physics: &PHYSICS_DEFAULT,
 };
 
 const STAGE_4_WIDTH: u8 = 30;
@@ -456,7 +928,228 @@ const STAGE_4: [u8; STAGE_4_WIDTH as usize*STAGE_4_HEIGHT as usize] = [
   b'#',b'#',b'#',b'#',b'#',b'#',b'#',b'#',b'#',b'#',b'#',b'#',b'#',b'#',b'#',b'#',b'#',b'#',b'#',b'#',b'#',b'#',b'#',b'#',b'#',b'#',b'#',b'#',b'#',b'#',
 ];
 const DAT_STAGE_4: StageData = StageData {
+  id: StageID::Stage4,
   width: STAGE_4_WIDTH,
   height: STAGE_4_HEIGHT,
   data: &STAGE_4,
-};
\ No newline at end of file
+  name: StrId::StageName4,
+  intro: &[],
+  cam_lock: None,
+  keep_broken: false,
+  checkpoint_keep: CHECKPOINT_KEEP_ALL,
+  respawn_hearts: true,
+  platforms: &[],
+  wall_jump: false,
+  physics: &PHYSICS_DEFAULT,
+};
+
+// Last Battle
+// 画面ちょうどの大きさの闘技場(カメラは固定)
+const STAGE_LAST_BATTLE_WIDTH: u8 = 10;
+const STAGE_LAST_BATTLE_HEIGHT: u8 = 10;
+const STAGE_LAST_BATTLE: [u8; STAGE_LAST_BATTLE_WIDTH as usize*STAGE_LAST_BATTLE_HEIGHT as usize] = [
+  b'#',b'#',b'#',b'#',b'#',b'#',b'#',b'#',b'#',b'#',
+  b'#',b' ',b' ',b' ',b' ',b' ',b' ',b' ',b' ',b'#',
+  b'#',b' ',b' ',b' ',b' ',b' ',b' ',b' ',b' ',b'#',
+  b'#',b' ',b' ',b' ',b' ',b' ',b' ',b' ',b' ',b'#',
+  b'#',b' ',b' ',b' ',b' ',b' ',b' ',b' ',b' ',b'#',
+  b'#',b' ',b' ',b' ',b' ',b' ',b' ',b' ',b' ',b'#',
+  b'#',b' ',b' ',b' ',b' ',b' ',b' ',b' ',b' ',b'#',
+  b'#',b' ',b' ',b' ',b' ',b' ',b' ',b' ',b' ',b'#',
+  b'#',b'@',b' ',b' ',b' ',b' ',b' ',b' ',b'g',b'#',
+  b'#',b'#',b'#',b'#',b'#',b'#',b'#',b'#',b'#',b'#',
+];
+// 弾を避けるための足場(左は上下に往復、右は四角く周回)
+const PLATFORMS_LAST_BATTLE: &[PlatformDef] = &[
+  PlatformDef { path: &[(1, 7), (1, 4)], mode: PathMode::PingPong, speed: 1, width: 1, solid_all: false },
+  PlatformDef { path: &[(7, 4), (8, 4), (8, 6), (7, 6)], mode: PathMode::Loop, speed: 1, width: 1, solid_all: false },
+];
+const DAT_STAGE_LAST_BATTLE: StageData = StageData {
+  id: StageID::LastBattle,
+  width: STAGE_LAST_BATTLE_WIDTH,
+  height: STAGE_LAST_BATTLE_HEIGHT,
+  data: &STAGE_LAST_BATTLE,
+  name: StrId::StageNameLastBattle,
+  intro: SCRIPT_LAST_BATTLE_INTRO,
+  cam_lock: Some(Vec2i::zero()),
+  keep_broken: false,
+  checkpoint_keep: CHECKPOINT_KEEP_ALL,
+  respawn_hearts: true,
+  platforms: PLATFORMS_LAST_BATTLE,
+  wall_jump: true,
+  physics: &PHYSICS_DEFAULT,
+};
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  // 仕掛けを1つずつ並べたテスト用のステージ
+  const TEST_DATA: &[u8] = b"\
+#########\
+#@xkfhpg#\
+#########";
+  const TEST_STAGE: StageData = StageData { width: 9, height: 3, data: TEST_DATA, intro: &[], cam_lock: None, ..DAT_STAGE_1 };
+  fn load(stage: &'static StageData) -> StageHandler {
+    let mut handler = StageHandler::new();
+    handler.now_stage = stage;
+    handler.reload();
+    handler
+  }
+  // 敵を置いたテスト用のステージ(歩く敵は壁の間を、飛ぶ敵は左右の壁の間を往復する)
+  const ENEMY_DATA: &[u8] = b"\
+#########\
+#  b    #\
+#@     g#\
+#w  #  t#\
+#########";
+  const ENEMY_STAGE: StageData = StageData { height: 5, data: ENEMY_DATA, ..TEST_STAGE };
+  fn find_entity(handler: &StageHandler, kind: EntityKind) -> Option<Entity> {
+    (0..MAX_ENTITY).map(|i| handler.entities.get(i)).find(|e| e.kind == kind)
+  }
+  fn cmd(cmd: InteractiveCmd) -> [Option<InteractiveCmd>; 4] { [Some(cmd), None, None, None] }
+
+  // 鍵を残す設定ならゴールも開いたまま、残さないならどちらも戻る
+  #[test]
+  fn checkpoint_keep_restores_key_and_goal_together() {
+    for keys in [true, false] {
+      for fragments in [true, false] {
+        let keep = CheckpointKeep { keys, fragments };
+        assert_eq!(keep.keeps(TileId::Key), keep.keeps_goal_opened(true));
+        assert!(!keep.keeps_goal_opened(false));
+        assert_eq!(keep.keeps(TileId::Fragment), fragments);
+      }
+    }
+  }
+
+  // 壊した壁は設定に従って死亡後も残るか元に戻る
+  #[test]
+  fn broken_wall_follows_keep_setting() {
+    const KEEP: StageData = StageData { keep_broken: true, ..TEST_STAGE };
+    const RESET: StageData = StageData { keep_broken: false, ..TEST_STAGE };
+    for (stage, after_death) in [(&KEEP, TileId::Empty), (&RESET, TileId::CrackedWall)] {
+      let mut handler = load(stage);
+      let idx = handler.get_idx_from_tile_pos(2, 1);
+      handler.update(cmd(InteractiveCmd::BreakWall(2, 1)));
+      assert_eq!(handler.tiles[idx].id, TileId::Empty);
+      handler.reload();
+      assert_eq!(handler.tiles[idx].id, after_death);
+    }
+  }
+
+  // 鍵を取ってからチェックポイントに触れて死ぬと、鍵は取ったままゴールも開いたまま
+  #[test]
+  fn checkpoint_respawn_keeps_key_and_goal() {
+    let mut handler = load(&TEST_STAGE);
+    let (key, cp) = (handler.get_idx_from_tile_pos(3, 1), handler.get_idx_from_tile_pos(6, 1));
+    handler.update(cmd(InteractiveCmd::GetKey(3, 1)));
+    handler.update(cmd(InteractiveCmd::TouchCheckpoint(6, 1)));
+    handler.reload();
+    assert_eq!(handler.get_respawn_pos(), Vec2i::new(6 * STAGE_TILE_SIZE as i16, STAGE_TILE_SIZE as i16));
+    assert_eq!(handler.tiles[cp].id, TileId::CheckpointActive);
+    assert_eq!(handler.tiles[key].id, TileId::Empty);
+    assert_eq!(handler.tiles[handler.goal_tile_idx].id, TileId::GoalOpened);
+    // 鍵を残さない設定なら、どちらも元に戻る
+    const RESET: StageData = StageData { checkpoint_keep: CheckpointKeep { keys: false, fragments: false }, ..TEST_STAGE };
+    let mut handler = load(&RESET);
+    handler.update(cmd(InteractiveCmd::GetKey(3, 1)));
+    handler.update(cmd(InteractiveCmd::TouchCheckpoint(6, 1)));
+    handler.reload();
+    assert_eq!(handler.tiles[key].id, TileId::Key);
+    assert_eq!(handler.tiles[handler.goal_tile_idx].id, TileId::GoalClosed);
+  }
+
+  // 取ったハートは設定に従って死亡後に戻るか消えたまま
+  #[test]
+  fn heart_follows_respawn_setting() {
+    const RESPAWN: StageData = StageData { respawn_hearts: true, ..TEST_STAGE };
+    const GONE: StageData = StageData { respawn_hearts: false, ..TEST_STAGE };
+    for (stage, after_death) in [(&RESPAWN, TileId::Heart), (&GONE, TileId::Empty)] {
+      let mut handler = load(stage);
+      let idx = handler.get_idx_from_tile_pos(5, 1);
+      handler.update(cmd(InteractiveCmd::GetHeart(5, 1)));
+      assert_eq!(handler.tiles[idx].id, TileId::Empty);
+      handler.reload();
+      assert_eq!(handler.tiles[idx].id, after_death);
+    }
+  }
+
+  // 歩く敵は壁で折り返し、フレーム数が一周しても動き続ける
+  #[test]
+  fn walker_turns_at_walls_and_keeps_walking() {
+    let mut handler = load(&ENEMY_STAGE);
+    let far = Vec2i::new(0, 0);
+    let mut turned = false;
+    for _ in 0..200 {
+      handler.update_entities(far);
+      let walker = find_entity(&handler, EntityKind::Walker).unwrap();
+      // 当たり判定が左の外壁と真ん中の壁の間に収まっている
+      let (x, _, w, _) = walker.get_rect();
+      assert!(x >= STAGE_TILE_SIZE as i16 && x + w <= 4 * STAGE_TILE_SIZE as i16);
+      turned |= walker.vel.x > 0;
+    }
+    assert!(turned);
+    // u16を一周させる
+    for i in 0..MAX_ENTITY {
+      let mut e = handler.entities.get(i);
+      e.frames = u16::MAX - 1;
+      handler.entities.set(i, e);
+    }
+    let before = find_entity(&handler, EntityKind::Walker).unwrap().pos;
+    for _ in 0..8 { handler.update_entities(far); }
+    assert_ne!(find_entity(&handler, EntityKind::Walker).unwrap().pos, before);
+  }
+
+  // 飛ぶ敵は上下に揺れながら進む
+  #[test]
+  fn flyer_moves_in_a_wave() {
+    let mut handler = load(&ENEMY_STAGE);
+    let origin = find_entity(&handler, EntityKind::Flyer).unwrap().pos;
+    let mut ys = Vec::new();
+    for _ in 0..80 {
+      handler.update_entities(Vec2i::zero());
+      ys.push(find_entity(&handler, EntityKind::Flyer).unwrap().pos.y);
+    }
+    assert!(ys.iter().any(|y| *y > origin.y) && ys.iter().any(|y| *y < origin.y));
+    assert_ne!(find_entity(&handler, EntityKind::Flyer).unwrap().pos.x, origin.x);
+  }
+
+  // 砲台は竜が近くにいるときだけ一定間隔で撃つ
+  #[test]
+  fn turret_fires_only_in_range() {
+    let mut handler = load(&ENEMY_STAGE);
+    let turret = find_entity(&handler, EntityKind::Turret).unwrap().pos;
+    let wait = 200; // 発射間隔より長く待つ
+    for _ in 0..wait { handler.update_entities(Vec2i::new(turret.x + 1000, turret.y)); }
+    assert!(find_entity(&handler, EntityKind::TurretShot).is_none());
+    let shot = (0..wait).find_map(|_| {
+      handler.update_entities(Vec2i::new(turret.x - 32, turret.y));
+      find_entity(&handler, EntityKind::TurretShot)
+    });
+    assert!(shot.is_some_and(|s| s.vel.x < 0));
+  }
+
+  // 乗られたら揺れてから消え、竜がどいてから戻る
+  #[test]
+  fn crumble_shakes_vanishes_and_respawns() {
+    let mut state = CrumbleState::Idle.next(false, false);
+    assert_eq!(state, CrumbleState::Idle);
+    state = state.next(true, false);
+    for _ in 0..CRUMBLE_SHAKE_TIME { state = state.next(false, false); }
+    assert_eq!(state, CrumbleState::Shake(CRUMBLE_SHAKE_TIME));
+    state = state.next(false, false);
+    assert_eq!(state, CrumbleState::Gone(0));
+    for _ in 0..CRUMBLE_RESPAWN_TIME { state = state.next(false, true); }
+    // 重なっている間は戻らない
+    assert_eq!(state.next(false, true), CrumbleState::Gone(CRUMBLE_RESPAWN_TIME));
+    assert_eq!(state.next(false, false), CrumbleState::Idle);
+  }
+
+  // 貯めた欠片はステージごとにu8のビットで覚えるので8個まで
+  #[test]
+  fn fragments_fit_in_bank_mask() {
+    for stage in DAT_STAGE_ALL {
+      assert!(stage.data.iter().filter(|c| **c == b'f').count() <= 8);
+    }
+  }
+}
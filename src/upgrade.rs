@@ -0,0 +1,161 @@
+use crate::wasm4::*;
+use crate::utils::*;
+use crate::lang::*;
+use crate::assets::img::FONT_MAIN;
+use crate::stage::STAGE_COUNT;
+
+// -------------------------------
+// 欠片で買える強化
+// -------------------------------
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Upgrade {
+  MaxHp,      // HPの最大値が増える
+  Flutter,    // 踏ん張りが長くなる
+  DoubleJump, // 空中でもう一度跳べる
+}
+pub const UPGRADE_ALL: [Upgrade; 3] = [Upgrade::MaxHp, Upgrade::Flutter, Upgrade::DoubleJump];
+pub const UPGRADE_DATA_SIZE: usize = 4 + STAGE_COUNT; // 使える欠片(1) + 各強化の段階(3) + 貯めた欠片(ステージ数)
+
+impl Upgrade {
+  pub fn get_max_level(self) -> u8 {
+    match self {
+      Upgrade::MaxHp      => 2,
+      Upgrade::Flutter    => 3,
+      Upgrade::DoubleJump => 1,
+    }
+  }
+  // 1段階上げるのに必要な欠片の数
+  pub fn get_cost(self) -> u8 {
+    match self {
+      Upgrade::MaxHp      => 2,
+      Upgrade::Flutter    => 1,
+      Upgrade::DoubleJump => 3,
+    }
+  }
+  pub fn get_name(self) -> StrId {
+    match self {
+      Upgrade::MaxHp      => StrId::UpgradeMaxHp,
+      Upgrade::Flutter    => StrId::UpgradeFlutter,
+      Upgrade::DoubleJump => StrId::UpgradeDoubleJump,
+    }
+  }
+}
+
+// 強化の台帳(セーブデータに保存される)
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct UpgradeLedger {
+  pub fragments: u8, // まだ使っていない欠片
+  levels: [u8; UPGRADE_ALL.len()],
+  banked: [u8; STAGE_COUNT], // ステージごとに貯めた欠片(同じ欠片を二度貯めないためのビット)
+}
+impl UpgradeLedger {
+  pub fn get_level(&self, upgrade: Upgrade) -> u8 { self.levels[upgrade as usize] }
+  pub fn has(&self, upgrade: Upgrade) -> bool { self.get_level(upgrade) > 0 }
+  pub fn can_buy(&self, upgrade: Upgrade) -> bool {
+    self.get_level(upgrade) < upgrade.get_max_level() && self.fragments >= upgrade.get_cost()
+  }
+  pub fn buy(&mut self, upgrade: Upgrade) -> bool {
+    if !self.can_buy(upgrade) { return false }
+    self.fragments -= upgrade.get_cost();
+    self.levels[upgrade as usize] += 1;
+    true
+  }
+  // まだ貯めていない欠片だけを貯める
+  pub fn deposit(&mut self, stage_idx: usize, mask: u8) {
+    let new = mask & !self.banked[stage_idx];
+    self.banked[stage_idx] |= new;
+    self.fragments = self.fragments.saturating_add(new.count_ones() as u8);
+  }
+  pub fn to_bytes(self) -> [u8; UPGRADE_DATA_SIZE] {
+    let mut buf = [0u8; UPGRADE_DATA_SIZE];
+    buf[0] = self.fragments;
+    buf[1..4].copy_from_slice(&self.levels);
+    buf[4..].copy_from_slice(&self.banked);
+    buf
+  }
+  // 範囲外の段階は最大値に丸める
+  pub fn from_bytes(buf: &[u8]) -> Self {
+    let mut ledger = Self { fragments: buf[0], ..Self::default() };
+    for (i, upgrade) in UPGRADE_ALL.iter().enumerate() {
+      ledger.levels[i] = buf[1 + i].min(upgrade.get_max_level());
+    }
+    ledger.banked.copy_from_slice(&buf[4..UPGRADE_DATA_SIZE]);
+    ledger
+  }
+}
+
+// -------------------------------
+// ステージの合間の強化メニュー
+// -------------------------------
+const MENU_ITEM_Y: i32 = 48;
+const MENU_ITEM_H: i32 = 14;
+const MENU_DONE_IDX: usize = UPGRADE_ALL.len(); // 最後の項目は「次へ」
+
+pub struct UpgradeMenu {
+  pub ledger: UpgradeLedger,
+  cursor: usize,
+  is_open: bool,
+}
+impl UpgradeMenu {
+  pub fn new() -> Self {
+    Self { ledger: UpgradeLedger::default(), cursor: 0, is_open: false }
+  }
+  pub fn is_open(&self) -> bool { self.is_open }
+  pub fn open(&mut self, ledger: UpgradeLedger) {
+    self.ledger = ledger;
+    self.cursor = MENU_DONE_IDX;
+    self.is_open = true;
+  }
+
+  pub fn update(&mut self) {
+    if !self.is_open { return }
+    if is_just_pressed(BTN_UP)   { self.cursor = (self.cursor + MENU_DONE_IDX) % (MENU_DONE_IDX + 1); }
+    if is_just_pressed(BTN_DOWN) { self.cursor = (self.cursor + 1) % (MENU_DONE_IDX + 1); }
+    if !is_just_pressed(BTN_Z) { return }
+    if self.cursor == MENU_DONE_IDX {
+      self.is_open = false;
+      return
+    }
+    // 買えたら高い音、買えなければ低い音
+    if self.ledger.buy(UPGRADE_ALL[self.cursor]) { tone(600 | (1200 << 16), 10, 50, TONE_PULSE1); }
+    else { tone(120, 8, 40, TONE_PULSE2); }
+  }
+
+  pub fn draw(&self) {
+    if !self.is_open { return }
+    FONT_MAIN.draw_center_x(tr(StrId::UpgradeTitle), 16);
+    FONT_MAIN.draw_center_x(&tr_fmt(StrId::UpgradeFragment, &[self.ledger.fragments as u32]), 30);
+    for (i, upgrade) in UPGRADE_ALL.iter().enumerate() {
+      let y = MENU_ITEM_Y + i as i32 * MENU_ITEM_H;
+      FONT_MAIN.draw(tr(upgrade.get_name()), 24, y);
+      let level = tr_fmt(StrId::UpgradeLevel, &[self.ledger.get_level(*upgrade) as u32, upgrade.get_max_level() as u32]);
+      FONT_MAIN.draw(&level, 136 - FONT_MAIN.get_text_width(&level), y);
+    }
+    FONT_MAIN.draw(tr(StrId::UpgradeDone), 24, MENU_ITEM_Y + MENU_DONE_IDX as i32 * MENU_ITEM_H);
+    // カーソル
+    FONT_MAIN.draw(">", 14, MENU_ITEM_Y + self.cursor as i32 * MENU_ITEM_H);
+    // 選んでいる強化の値段
+    if let Some(upgrade) = UPGRADE_ALL.get(self.cursor) {
+      let msg = if self.ledger.get_level(*upgrade) >= upgrade.get_max_level() { tr(StrId::UpgradeMaxed).to_string() }
+        else { tr_fmt(StrId::UpgradeCost, &[upgrade.get_cost() as u32]) };
+      FONT_MAIN.draw_center_x(&msg, 120);
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  // 同じ欠片を持ち帰っても二度は貯まらない
+  #[test]
+  fn deposit_banks_each_fragment_once() {
+    let mut ledger = UpgradeLedger::default();
+    ledger.deposit(0, 0b01);
+    ledger.deposit(0, 0b11);
+    ledger.deposit(0, 0b11);
+    ledger.deposit(1, 0b01);
+    assert_eq!(ledger.fragments, 3);
+    assert_eq!(UpgradeLedger::from_bytes(&ledger.to_bytes()), ledger);
+  }
+}
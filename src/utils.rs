@@ -17,6 +17,7 @@ pub const BTN_DOWN:  u8 = 7;
 
 pub struct ButtonHandler {
   btn_states: [u16; 8],
+  since_pressed: [u16; 8], // 最後に押されてからのフレーム数(先行入力用)
   old_btn_states: u8,
   just_changed_btn_states: u8
 }
@@ -33,6 +34,14 @@ pub fn is_pressed(btn: u8) -> bool {
 pub fn is_just_pressed(btn: u8) -> bool {
   BTN_HANDLER.lock().expect("btn_state").btn_states[btn as usize] == 1
 }
+// 最後に押された瞬間から何フレーム経ったか
+pub fn get_frames_since_pressed(btn: u8) -> u16 {
+  BTN_HANDLER.lock().expect("btn_state").since_pressed[btn as usize]
+}
+// 先行入力を使ったら、同じ入力で二度反応しないように消す
+pub fn consume_press(btn: u8) {
+  BTN_HANDLER.lock().expect("btn_state").since_pressed[btn as usize] = u16::MAX;
+}
 pub fn is_just_released(btn: u8) -> bool {
   let handler = BTN_HANDLER.lock().expect("btn_state");
   // 「ボタンの押された長さが0である」かつ、「直近にボタンの状態が変化した」であれば離された瞬間
@@ -42,7 +51,7 @@ pub fn is_just_released(btn: u8) -> bool {
 
 impl ButtonHandler {
   pub fn new() -> Self {
-    Self { btn_states: [0,0,0,0,0,0,0,0], old_btn_states: 0, just_changed_btn_states: 0}
+    Self { btn_states: [0,0,0,0,0,0,0,0], since_pressed: [u16::MAX; 8], old_btn_states: 0, just_changed_btn_states: 0}
   }
   pub fn update(&mut self) {
     let gamepad = unsafe{*GAMEPAD1};
@@ -55,6 +64,10 @@ impl ButtonHandler {
     if gamepad & BUTTON_RIGHT != 0 { self.btn_states[5] += 1 } else { self.btn_states[5] = 0 }
     if gamepad & BUTTON_UP    != 0 { self.btn_states[6] += 1 } else { self.btn_states[6] = 0 }
     if gamepad & BUTTON_DOWN  != 0 { self.btn_states[7] += 1 } else { self.btn_states[7] = 0 }
+    for i in 0..self.btn_states.len() {
+      if self.btn_states[i] == 1 { self.since_pressed[i] = 0; }
+      else { self.since_pressed[i] = self.since_pressed[i].saturating_add(1); }
+    }
 
     self.old_btn_states = gamepad;
   }
@@ -99,6 +112,11 @@ impl Image {
     set_drawcolor_idx(self.col_idx);
     blit(self.raw.data, x, y, self.raw.width, self.raw.height, self.raw.flags | flags);
   }
+  // 色を差し替えて描画(被弾時の点滅など)
+  pub fn drawfc(&self, x: i32, y: i32, flags: u32, col_idx: u16) {
+    set_drawcolor_idx(col_idx);
+    blit(self.raw.data, x, y, self.raw.width, self.raw.height, self.raw.flags | flags);
+  }
   // 画像そのものの描画フラグで描画(一部のみ)
   pub fn draw_sub(&self, x: i32, y: i32, w: u32, h: u32, sx: u32, sy: u32) {
     set_drawcolor_idx(self.col_idx);
@@ -141,6 +159,9 @@ impl Timeline {
   pub fn drawf(&self, x: i32, y: i32, flags: u32) {
     self.images[self.now_idx as usize].drawf(x, y, flags);
   }
+  pub fn get_image(&self) -> &'static Image {
+    self.images[self.now_idx as usize]
+  }
   pub fn reset(&mut self) {
     self.now_idx = 0;
     self.frame_count = 0;
@@ -151,7 +172,7 @@ impl Timeline {
 // -------------------------------
 // Particle
 // -------------------------------
-const MAX_PARTICLE_VALUE: u8 = 32;
+pub const MAX_PARTICLE_VALUE: usize = 32;
 #[derive(Copy, Clone)]
 pub struct Particle {
   pub alive: bool,
@@ -181,18 +202,6 @@ impl Particle {
 
 }
 
-// -------------------------------
-// Text
-// -------------------------------
-pub fn text_center_x<T: AsRef<[u8]>>(msg: T, y: i32) {
-  let msg_ref = msg.as_ref();
-  let x = ((160 - (msg_ref.len()*8)) / 2) as i32;
-  text(msg, x, y);
-}
-pub fn text_center_y<T: AsRef<[u8]>>(msg: T, x: i32) {
-  let y = 160 - (8 / 2);
-  text(msg, x, y);
-}
 // -------------------------------
 // Color
 // -------------------------------
@@ -202,6 +211,11 @@ pub fn set_drawcolor(fcol: u16, scol: u16) {
 pub fn set_drawcolor_idx(idx: u16) {
   unsafe { *DRAW_COLORS = idx }
 }
+// WASM-4の初期パレット
+pub const DEFAULT_PALETTE: [u32; 4] = [0xe0f8cf, 0x86c06c, 0x306850, 0x071821];
+pub fn set_palette(palette: &[u32; 4]) {
+  unsafe { *PALETTE = *palette; }
+}
 
 // -------------------------------
 // Math
@@ -212,13 +226,17 @@ pub struct Vec2i {
   pub y: i16
 }
 impl Vec2i {
-  pub fn new(x: i16, y: i16) -> Self {
+  pub const fn new(x: i16, y: i16) -> Self {
     Vec2i { x, y }
   }
-  pub fn zero() -> Self {
+  pub const fn zero() -> Self {
     Vec2i { x: 0, y: 0 }
   }
 }
+// 矩形(x, y, w, h)同士が重なっているか(当たり判定は全てここを通す)
+pub fn is_overlap(a: (i16, i16, i16, i16), b: (i16, i16, i16, i16)) -> bool {
+  a.0 < b.0 + b.2 && b.0 < a.0 + a.2 && a.1 < b.1 + b.3 && b.1 < a.1 + a.3
+}
 
 pub struct Clock {
   wait_frame: u16,
@@ -237,3 +255,18 @@ impl Clock {
     self.now_frame = 0;
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  // 辺が接しているだけなら重なっていない
+  #[test]
+  fn is_overlap_excludes_touching_edges() {
+    let a = (0, 0, 16, 16);
+    assert!(is_overlap(a, (15, 15, 4, 4)));
+    assert!(is_overlap(a, (-4, 4, 8, 8)));
+    assert!(!is_overlap(a, (16, 0, 4, 4)));
+    assert!(!is_overlap(a, (0, -4, 4, 4)));
+  }
+}
@@ -0,0 +1,31 @@
+// -------------------------------
+// テスト用のWASM-4関数
+// -------------------------------
+// ネイティブでテストを動かすとき、本来はランタイムが用意する関数を何もしない関数で置き換える
+// (メモリマップのレジスタには触れないので、入力や描画を読むコードはテストから呼ばないこと)
+
+#[no_mangle]
+extern "C" fn blit(_sprite: *const u8, _x: i32, _y: i32, _width: u32, _height: u32, _flags: u32) {}
+#[allow(clippy::too_many_arguments)]
+#[no_mangle]
+extern "C" fn blitSub(_sprite: *const u8, _x: i32, _y: i32, _width: u32, _height: u32, _src_x: u32, _src_y: u32, _stride: u32, _flags: u32) {}
+#[no_mangle]
+extern "C" fn line(_x1: i32, _y1: i32, _x2: i32, _y2: i32) {}
+#[no_mangle]
+extern "C" fn oval(_x: i32, _y: i32, _width: u32, _height: u32) {}
+#[no_mangle]
+extern "C" fn rect(_x: i32, _y: i32, _width: u32, _height: u32) {}
+#[no_mangle]
+extern "C" fn textUtf8(_text: *const u8, _length: usize, _x: i32, _y: i32) {}
+#[no_mangle]
+extern "C" fn vline(_x: i32, _y: i32, _len: u32) {}
+#[no_mangle]
+extern "C" fn hline(_x: i32, _y: i32, _len: u32) {}
+#[no_mangle]
+extern "C" fn tone(_frequency: u32, _duration: u32, _volume: u32, _flags: u32) {}
+#[no_mangle]
+extern "C" fn diskr(_dest: *mut u8, _size: u32) -> u32 { 0 }
+#[no_mangle]
+extern "C" fn diskw(_src: *const u8, size: u32) -> u32 { size }
+#[no_mangle]
+extern "C" fn traceUtf8(_trace: *const u8, _length: usize) {}